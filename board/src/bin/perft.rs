@@ -0,0 +1,37 @@
+use std::time::Instant;
+
+use anyhow::{anyhow, Result};
+
+use myopic_board::perft::{divide_cached, PerftTable};
+use myopic_board::{Board, ChessBoard};
+
+const TABLE_SIZE: usize = 1_000_000;
+
+/// Usage: perft <depth> [fen]
+///
+/// Prints the node count beneath each legal move in the given position
+/// (the start position if no FEN is supplied) followed by the total so
+/// the output can be diffed against a reference engine.
+fn main() -> Result<()> {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let depth = args
+        .get(0)
+        .ok_or(anyhow!("Usage: perft <depth> [fen]"))?
+        .parse::<usize>()?;
+    let mut board = match args.len() {
+        1 => myopic_board::start(),
+        _ => args[1..].join(" ").parse::<Board>()?,
+    };
+    let mut table = PerftTable::new(TABLE_SIZE)?;
+    let start = Instant::now();
+    let division = divide_cached(&mut board, depth, &mut table)?;
+    for (mv, count) in division.iter() {
+        println!("{}: {}", mv.uci_format(), count);
+    }
+    println!();
+    println!("Moves: {}", division.len());
+    println!("Nodes: {}", division.iter().map(|(_, n)| n).sum::<u64>());
+    println!("Time: {}ms", start.elapsed().as_millis());
+    println!("Fen: {}", board.to_fen());
+    Ok(())
+}
@@ -0,0 +1,200 @@
+use std::fmt::{Display, Formatter};
+
+use anyhow::{anyhow, Result};
+
+use myopic_core::{Side, Square};
+
+use crate::{CastleZone, ChessBoard, Move, Piece};
+
+const SQUARE_MASK: u16 = 0b11_1111;
+const DEST_SHIFT: u16 = 6;
+const FLAG_SHIFT: u16 = 12;
+
+const STANDARD: u16 = 0;
+const ENPASSANT: u16 = 1;
+const CASTLE: u16 = 2;
+/// The four promotion flags are offset from this value in the order
+/// knight, bishop, rook, queen.
+const PROMOTION: u16 = 4;
+
+#[rustfmt::skip]
+const PROMOTION_TARGETS: [[Piece; 4]; 2] = [
+    [Piece::WN, Piece::WB, Piece::WR, Piece::WQ],
+    [Piece::BN, Piece::BB, Piece::BR, Piece::BQ],
+];
+
+/// A move packed into sixteen bits, the lowest six bits hold the origin
+/// square, the next six hold the destination square and the highest four
+/// hold a flag distinguishing standard, enpassant, castling and each
+/// type of promotion. Castling moves are encoded with the squares the
+/// king moves between. Unlike a full [Move] this does not record the
+/// position it was created in or the pieces involved so it is cheap to
+/// store and copy, the full move can be recovered losslessly with the
+/// board it was created for.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CompactMove(u16);
+
+impl CompactMove {
+    /// The square the moving piece starts on.
+    pub fn origin(self) -> Square {
+        Square::from_index((self.0 & SQUARE_MASK) as usize)
+    }
+
+    /// The square the moving piece finishes on.
+    pub fn dest(self) -> Square {
+        Square::from_index(((self.0 >> DEST_SHIFT) & SQUARE_MASK) as usize)
+    }
+
+    /// Recover the full move in the context of the given position which
+    /// must be the position the original move was created in. An error
+    /// is returned if the encoded move is not consistent with the pieces
+    /// on the board, no check is made that the resulting move is legal.
+    pub fn expand<B: ChessBoard>(self, board: &B) -> Result<Move> {
+        let (source, active) = (board.hash(), board.active());
+        let (from, dest) = (self.origin(), self.dest());
+        let moving = board
+            .piece(from)
+            .filter(|p| p.side() == active)
+            .ok_or_else(|| anyhow!("No {} piece on {} for move {}", active, from, self))?;
+        let capture = board.piece(dest);
+        let flag = self.0 >> FLAG_SHIFT;
+        // A Chess960 king may castle onto the square of its own rook
+        if flag != CASTLE && capture.map(|p| p.side() == active).unwrap_or(false) {
+            return Err(anyhow!("Move {} captures a {} piece", self, active));
+        }
+        match flag {
+            STANDARD => Ok(Move::Standard {
+                source,
+                moving,
+                from,
+                dest,
+                capture,
+            }),
+            ENPASSANT => {
+                if board.enpassant() != Some(dest) {
+                    Err(anyhow!("Move {} is not a legal enpassant target", self))
+                } else {
+                    Ok(Move::Enpassant {
+                        source,
+                        side: active,
+                        from,
+                        dest,
+                        capture: match active {
+                            Side::White => dest >> 8,
+                            Side::Black => dest << 8,
+                        },
+                    })
+                }
+            }
+            CASTLE => {
+                let castling = board.castling();
+                let zone = [CastleZone::kingside(active), CastleZone::queenside(active)]
+                    .iter()
+                    .cloned()
+                    .find(|&z| {
+                        let (_, king_src, king_dest) = castling.king_data(z);
+                        king_src == from && king_dest == dest
+                    })
+                    .ok_or_else(|| anyhow!("Move {} is not a castling move", self))?;
+                Ok(Move::Castle {
+                    source,
+                    zone,
+                    castling,
+                })
+            }
+            flag => Ok(Move::Promotion {
+                source,
+                from,
+                dest,
+                promoted: PROMOTION_TARGETS[active as usize][(flag - PROMOTION) as usize],
+                capture,
+            }),
+        }
+    }
+}
+
+impl From<&Move> for CompactMove {
+    fn from(mv: &Move) -> Self {
+        let (from, dest, flag) = match *mv {
+            Move::Standard { from, dest, .. } => (from, dest, STANDARD),
+            Move::Enpassant { from, dest, .. } => (from, dest, ENPASSANT),
+            Move::Castle { zone, castling, .. } => {
+                let (_, from, dest) = castling.king_data(zone);
+                (from, dest, CASTLE)
+            }
+            Move::Promotion {
+                from,
+                dest,
+                promoted,
+                ..
+            } => (from, dest, PROMOTION + (promoted as u16 % 6) - 1),
+        };
+        CompactMove((from as u16) | (dest as u16) << DEST_SHIFT | flag << FLAG_SHIFT)
+    }
+}
+
+impl Display for CompactMove {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.origin(), self.dest())?;
+        match self.0 >> FLAG_SHIFT {
+            STANDARD => Ok(()),
+            ENPASSANT => write!(f, "e"),
+            CASTLE => write!(f, "c"),
+            flag => write!(f, "{}", ['n', 'b', 'r', 'q'][(flag - PROMOTION) as usize]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+
+    use myopic_core::Reflectable;
+
+    use crate::{Board, ChessBoard, MoveComputeType};
+
+    use super::*;
+
+    fn round_trip(fen: &str) -> Result<()> {
+        let board = fen.parse::<Board>()?;
+        for mut board in vec![board.clone(), board.reflect()] {
+            let moves = board.compute_moves(MoveComputeType::All);
+            assert!(!moves.is_empty());
+            for mv in moves {
+                let compact = CompactMove::from(&mv);
+                assert_eq!(mv, compact.expand(&board)?, "{} in {}", compact, fen);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip_castling_and_captures() -> Result<()> {
+        round_trip("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")
+    }
+
+    #[test]
+    fn round_trip_promotions() -> Result<()> {
+        round_trip("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1")
+    }
+
+    #[test]
+    fn round_trip_enpassant() -> Result<()> {
+        round_trip("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3")
+    }
+
+    #[test]
+    fn expand_in_wrong_position() -> Result<()> {
+        let mut board = crate::start();
+        let mv = CompactMove::from(&board.parse_uci("e2e4")?);
+        assert_eq!("e2e4", mv.to_string());
+        board.play_uci("e2e4")?;
+        assert!(mv.expand(&board).is_err());
+        board.play_uci("d7d5")?;
+        let enpassant = CompactMove::from(&board.parse_uci("e4d5")?);
+        assert!(CompactMove(enpassant.0 | ENPASSANT << FLAG_SHIFT)
+            .expand(&board)
+            .is_err());
+        Ok(())
+    }
+}
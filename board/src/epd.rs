@@ -0,0 +1,402 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error, Result};
+
+use crate::{Board, ChessBoard, FenComponent, Move};
+
+/// The typed operand(s) of a single EPD operation. Moves are resolved
+/// against the position the operation is attached to, for a principal
+/// variation each move is resolved in the position reached by the
+/// preceding moves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operation {
+    /// bm: the best moves in the position
+    BestMoves(Vec<Move>),
+    /// am: the moves to avoid in the position
+    AvoidMoves(Vec<Move>),
+    /// id: the identifier of the position
+    Id(String),
+    /// c0-c9: a comment
+    Comment(String),
+    /// ce: the centipawn evaluation from the perspective of the active side
+    CentipawnEval(i32),
+    /// pv: the predicted principal variation
+    PrincipalVariation(Vec<Move>),
+    /// hmvc: the half move clock
+    HalfMoveClock(usize),
+    /// fmvn: the full move number
+    FullMoveNumber(usize),
+    /// Any other opcode with its raw operands
+    Other(Vec<String>),
+}
+
+/// A position encoded in Extended Position Description format along with
+/// its operations keyed by opcode.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Epd {
+    pub board: Board,
+    pub operations: BTreeMap<String, Operation>,
+}
+
+impl FromStr for Epd {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let fields = s.split_whitespace().take(4).collect::<Vec<_>>();
+        if fields.len() < 4 {
+            return Err(anyhow!("Cannot parse EPD {}", s));
+        }
+        let mut raw_operations = vec![];
+        let mut rest = s;
+        for field in fields.iter() {
+            rest = rest.trim_start()[field.len()..].trim_start();
+        }
+        for (opcode, operands) in split_operations(rest)? {
+            raw_operations.push((opcode, operands));
+        }
+        let operand = |code: &str| {
+            raw_operations
+                .iter()
+                .find(|(opcode, _)| opcode == code)
+                .and_then(|(_, operands)| operands.first())
+        };
+        let clock = operand("hmvc").map_or("0", |s| s.as_str());
+        let move_number = operand("fmvn").map_or("1", |s| s.as_str());
+        let mut board = format!("{} {} {}", fields.join(" "), clock, move_number)
+            .parse::<Board>()
+            .map_err(|_| anyhow!("Cannot parse EPD {}", s))?;
+
+        let mut operations = BTreeMap::new();
+        for (opcode, operands) in raw_operations {
+            let operation = parse_operation(&mut board, opcode.as_str(), operands)?;
+            operations.insert(opcode, operation);
+        }
+        Ok(Epd { board, operations })
+    }
+}
+
+impl Epd {
+    /// Create an EPD record for the given position with no operations.
+    pub fn new(board: Board) -> Epd {
+        Epd {
+            board,
+            operations: BTreeMap::new(),
+        }
+    }
+
+    /// Return the best moves in this position if the bm opcode is present.
+    pub fn best_moves(&self) -> Option<&Vec<Move>> {
+        match self.operations.get("bm") {
+            Some(Operation::BestMoves(mvs)) => Some(mvs),
+            _ => None,
+        }
+    }
+
+    /// Return the moves to avoid in this position if the am opcode is present.
+    pub fn avoid_moves(&self) -> Option<&Vec<Move>> {
+        match self.operations.get("am") {
+            Some(Operation::AvoidMoves(mvs)) => Some(mvs),
+            _ => None,
+        }
+    }
+
+    /// Return the identifier of this position if the id opcode is present.
+    pub fn id(&self) -> Option<&str> {
+        match self.operations.get("id") {
+            Some(Operation::Id(id)) => Some(id.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Encode this record as an EPD string with the operations ordered by
+    /// opcode, moves are written in standard algebraic notation. The half
+    /// move clock and full move number of the board are written as hmvc
+    /// and fmvn operations when they differ from the defaults assumed on
+    /// parsing, unless those operations are already present.
+    pub fn to_epd(&self) -> Result<String> {
+        let mut dest = self.board.to_partial_fen(&[
+            FenComponent::Board,
+            FenComponent::Active,
+            FenComponent::CastlingRights,
+            FenComponent::Enpassant,
+        ]);
+        let mut operations = self.operations.clone();
+        let clock = self.board.half_move_clock();
+        if clock != 0 {
+            operations
+                .entry("hmvc".to_owned())
+                .or_insert(Operation::HalfMoveClock(clock));
+        }
+        let move_number = self.board.position_count() / 2 + 1;
+        if move_number != 1 {
+            operations
+                .entry("fmvn".to_owned())
+                .or_insert(Operation::FullMoveNumber(move_number));
+        }
+        for (opcode, operation) in operations.iter() {
+            let operands = match operation {
+                Operation::BestMoves(mvs) | Operation::AvoidMoves(mvs) => {
+                    let mut board = self.board.clone();
+                    let mut sans = vec![];
+                    for mv in mvs.iter() {
+                        sans.push(mv.to_san(&mut board)?);
+                    }
+                    sans
+                }
+                Operation::PrincipalVariation(mvs) => {
+                    let mut board = self.board.clone();
+                    let mut sans = vec![];
+                    for mv in mvs.iter() {
+                        sans.push(mv.to_san(&mut board)?);
+                        board.make(mv.clone())?;
+                    }
+                    sans
+                }
+                Operation::Id(s) | Operation::Comment(s) => vec![quote(s)],
+                Operation::CentipawnEval(n) => vec![n.to_string()],
+                Operation::HalfMoveClock(n) | Operation::FullMoveNumber(n) => vec![n.to_string()],
+                Operation::Other(operands) => operands.clone(),
+            };
+            dest.push(' ');
+            dest.push_str(opcode.as_str());
+            for operand in operands {
+                dest.push(' ');
+                dest.push_str(operand.as_str());
+            }
+            dest.push(';');
+        }
+        Ok(dest)
+    }
+}
+
+fn parse_operation(board: &mut Board, opcode: &str, operands: Vec<String>) -> Result<Operation> {
+    let single = |operands: &Vec<String>| match operands.as_slice() {
+        [operand] => Ok(operand.clone()),
+        _ => Err(anyhow!("Expected one operand for {}", opcode)),
+    };
+    Ok(match opcode {
+        "bm" => Operation::BestMoves(parse_moves(board, &operands)?),
+        "am" => Operation::AvoidMoves(parse_moves(board, &operands)?),
+        "pv" => Operation::PrincipalVariation(parse_variation(board, &operands)?),
+        "id" => Operation::Id(unquote(single(&operands)?)),
+        "ce" => Operation::CentipawnEval(single(&operands)?.parse()?),
+        "hmvc" => Operation::HalfMoveClock(single(&operands)?.parse()?),
+        "fmvn" => Operation::FullMoveNumber(single(&operands)?.parse()?),
+        c if c.len() == 2 && c.starts_with('c') && c.ends_with(|d: char| d.is_ascii_digit()) => {
+            Operation::Comment(unquote(single(&operands)?))
+        }
+        _ => Operation::Other(operands),
+    })
+}
+
+/// Parse a move encoded in standard algebraic notation, falling back to
+/// uci format which some test suites use instead.
+fn parse_move(board: &mut Board, encoded: &str) -> Result<Move> {
+    let san = encoded.replace(|c: char| "+#!?".contains(c), "");
+    crate::parse::pgn::parse_single_move(board, san.as_str())
+        .or_else(|_| board.parse_uci(encoded))
+        .map_err(|_| anyhow!("Cannot parse {} in {}", encoded, board.to_fen()))
+}
+
+fn parse_moves(board: &mut Board, operands: &[String]) -> Result<Vec<Move>> {
+    operands.iter().map(|mv| parse_move(board, mv)).collect()
+}
+
+fn parse_variation(board: &mut Board, operands: &[String]) -> Result<Vec<Move>> {
+    let mut variation_board = board.clone();
+    let mut dest = vec![];
+    for operand in operands {
+        let mv = parse_move(&mut variation_board, operand)?;
+        variation_board.make(mv.clone())?;
+        dest.push(mv);
+    }
+    Ok(dest)
+}
+
+/// Wrap a string operand in quotes, escaping any quotes or backslashes
+/// it contains with a backslash.
+fn quote(operand: &str) -> String {
+    let escaped = operand.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("\"{}\"", escaped)
+}
+
+fn unquote(operand: String) -> String {
+    match operand.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+        Some(unquoted) => {
+            let mut dest = String::new();
+            let mut chars = unquoted.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => dest.extend(chars.next()),
+                    c => dest.push(c),
+                }
+            }
+            dest
+        }
+        None => operand,
+    }
+}
+
+/// Splits the operations section of an EPD string into opcodes and their
+/// operands, quoted operands may contain whitespace and semicolons along
+/// with quotes escaped by a backslash.
+fn split_operations(operations: &str) -> Result<Vec<(String, Vec<String>)>> {
+    let mut dest = vec![];
+    let mut tokens: Vec<String> = vec![];
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut chars = operations.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if quoted => {
+                current.push(c);
+                current.extend(chars.next());
+            }
+            '"' => {
+                quoted = !quoted;
+                current.push(c);
+            }
+            ';' if !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+                if tokens.is_empty() {
+                    return Err(anyhow!("Empty operation in {}", operations));
+                }
+                let opcode = tokens.remove(0);
+                dest.push((opcode, std::mem::take(&mut tokens)));
+            }
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if quoted || !current.is_empty() || !tokens.is_empty() {
+        Err(anyhow!("Unterminated operation in {}", operations))
+    } else {
+        Ok(dest)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn uci(mvs: &[Move]) -> Vec<String> {
+        mvs.iter().map(|m| m.uci_format()).collect()
+    }
+
+    #[test]
+    fn best_move_and_id() -> Result<()> {
+        let epd = "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id \"WAC.001\";"
+            .parse::<Epd>()?;
+        assert_eq!(vec!["g3g6"], uci(epd.best_moves().unwrap()));
+        assert_eq!(Some("WAC.001"), epd.id());
+        assert_eq!(None, epd.avoid_moves());
+        assert_eq!(
+            "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - 0 1",
+            epd.board.to_fen()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn all_standard_opcodes() -> Result<()> {
+        let encoded = "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - \
+            am Ke2 Qe2; bm Bb5 Bc4; c0 \"Open game; two knights\"; ce 35; fmvn 3; \
+            hmvc 2; id \"test.1\"; pv Bb5 a6 Ba4;";
+        let epd = encoded.parse::<Epd>()?;
+        assert_eq!(vec!["f1b5", "f1c4"], uci(epd.best_moves().unwrap()));
+        assert_eq!(vec!["e1e2", "d1e2"], uci(epd.avoid_moves().unwrap()));
+        assert_eq!(
+            Some(&Operation::Comment("Open game; two knights".to_owned())),
+            epd.operations.get("c0")
+        );
+        assert_eq!(
+            Some(&Operation::CentipawnEval(35)),
+            epd.operations.get("ce")
+        );
+        assert_eq!(
+            Some(&Operation::HalfMoveClock(2)),
+            epd.operations.get("hmvc")
+        );
+        assert_eq!(
+            Some(&Operation::FullMoveNumber(3)),
+            epd.operations.get("fmvn")
+        );
+        match epd.operations.get("pv") {
+            Some(Operation::PrincipalVariation(mvs)) => {
+                assert_eq!(vec!["f1b5", "a7a6", "b5a4"], uci(mvs))
+            }
+            _ => panic!(),
+        }
+        assert_eq!(
+            "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3",
+            epd.board.to_fen()
+        );
+        assert_eq!(encoded, epd.to_epd()?);
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_opcode_is_preserved() -> Result<()> {
+        let encoded = "8/8/8/8/8/8/k7/K7 w - - acd 12; sm Kb1;";
+        let epd = encoded.parse::<Epd>()?;
+        assert_eq!(
+            Some(&Operation::Other(vec!["12".to_owned()])),
+            epd.operations.get("acd")
+        );
+        assert_eq!(encoded, epd.to_epd()?);
+        Ok(())
+    }
+
+    #[test]
+    fn from_board() -> Result<()> {
+        let mut board = crate::start();
+        board.play_uci("e2e4 c7c5")?;
+        assert_eq!(
+            "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 fmvn 2;",
+            Epd::new(board).to_epd()?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn move_counters_and_escaped_quotes_round_trip() -> Result<()> {
+        let mut board = crate::start();
+        board.play_uci("g1f3 g8f6 f3g1 f6g8")?;
+        let mut epd = Epd::new(board);
+        epd.operations.insert(
+            "id".to_owned(),
+            Operation::Id("the \"knight\" dance \\".to_owned()),
+        );
+        epd.operations
+            .insert("c0".to_owned(), Operation::Comment("\"; a\"".to_owned()));
+        let encoded = epd.to_epd()?;
+        assert_eq!(
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - \
+            c0 \"\\\"; a\\\"\"; fmvn 3; hmvc 4; id \"the \\\"knight\\\" dance \\\\\";",
+            encoded
+        );
+        let decoded = encoded.parse::<Epd>()?;
+        assert_eq!(epd.board.to_fen(), decoded.board.to_fen());
+        assert_eq!(Some("the \"knight\" dance \\"), decoded.id());
+        assert_eq!(encoded, decoded.to_epd()?);
+        Ok(())
+    }
+
+    #[test]
+    fn malformed() {
+        assert!("8/8/8/8/8/8/k7/K7 w -".parse::<Epd>().is_err());
+        assert!("8/8/8/8/8/8/k7/K7 w - - bm Kb1".parse::<Epd>().is_err());
+        assert!("8/8/8/8/8/8/k7/K7 w - - bm Kc3;".parse::<Epd>().is_err());
+        assert!("8/8/8/8/8/8/k7/K7 w - - id \"unterminated;"
+            .parse::<Epd>()
+            .is_err());
+    }
+}
@@ -76,7 +76,7 @@ impl Board {
             _ => match &self.cache.move_constraints {
                 Some(x) => x.clone(),
                 None => {
-                    let result = self.compute_constraints(computation_type);
+                    let result = self.compute_constraints(MoveComputeType::All);
                     self.cache.move_constraints = Some(result.clone());
                     result
                 }
@@ -91,7 +91,9 @@ impl Board {
             self.check(passive_control, &pinned)
         } else {
             match computation_type {
-                MoveComputeType::All => self.any(passive_control, &pinned),
+                MoveComputeType::All | MoveComputeType::Captures | MoveComputeType::Quiets => {
+                    self.any(passive_control, &pinned)
+                }
                 MoveComputeType::Attacks => self.attacks(passive_control, &pinned, false),
                 MoveComputeType::AttacksChecks => self.attacks(passive_control, &pinned, true),
             }
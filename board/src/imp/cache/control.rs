@@ -1,5 +1,6 @@
 use myopic_core::*;
 
+use crate::ChessBoard;
 use crate::imp::Board;
 
 impl Board {
@@ -14,6 +15,28 @@ impl Board {
         }
     }
 
+    /// Computes the pieces of the given side which control the given
+    /// square by looking outwards from the square with the movement of
+    /// the opposing pieces.
+    pub fn attackers_to_impl(&self, square: Square, side: Side) -> BitBoard {
+        let (whites, blacks) = self.sides();
+        Piece::of(side)
+            .map(|p| self.pieces.locs(p) & p.reflect().control(square, whites, blacks))
+            .collect()
+    }
+
+    pub fn checkers_impl(&mut self) -> BitBoard {
+        match &self.cache.checkers {
+            Some(x) => *x,
+            None => {
+                let king = self.pieces.king_location(self.active);
+                let result = self.attackers_to_impl(king, self.active.reflect());
+                self.cache.checkers = Some(result);
+                result
+            }
+        }
+    }
+
     /// Computes the total area of control on the board for a given side. Note
     /// that the passive king is treated as invisible so that if it is in
     /// check it cannot create it's own escape squares by blocking the
@@ -41,7 +64,7 @@ impl Board {
 
 #[cfg(test)]
 mod test {
-    use crate::{BitBoard, constants::*, Side};
+    use crate::{BitBoard, ChessBoard, constants::*, Reflectable, Side, Square};
     use crate::enumset::EnumSet;
     use crate::imp::Board;
     use crate::imp::test::TestBoard;
@@ -59,6 +82,25 @@ mod test {
         );
     }
 
+    #[test]
+    fn attackers_and_checkers() {
+        let mut board = "4k3/8/8/8/8/3n4/2P5/r3K2R w K - 0 1"
+            .parse::<Board>()
+            .unwrap();
+        assert_eq!(A1 | D3, board.checkers());
+        assert_eq!(C2, board.attackers_to(Square::D3, Side::White));
+        assert_eq!(D3, board.attackers_to(Square::F2, Side::Black));
+        assert_eq!(E1, board.attackers_to(Square::D1, Side::White));
+        assert_eq!(H1, board.attackers_to(Square::G1, Side::White));
+        assert_eq!(A1, board.attackers_to(Square::B1, Side::Black));
+        let mut reflected = board.reflect();
+        assert_eq!((A1 | D3).reflect(), reflected.checkers());
+        assert_eq!(
+            C2.reflect(),
+            reflected.attackers_to(Square::D3.reflect(), Side::Black)
+        );
+    }
+
     fn get_test_board() -> TestBoard {
         TestBoard {
             whites: vec![
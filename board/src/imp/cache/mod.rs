@@ -17,6 +17,9 @@ pub struct CalculationCache {
     passive_control: Option<BitBoard>,
     pinned_set: Option<RaySet>,
     move_constraints: Option<MoveConstraints>,
+    checkers: Option<BitBoard>,
+    passive_pinned: Option<BitBoard>,
+    discoveries: Option<RaySet>,
 }
 
 impl Board {
@@ -25,5 +28,8 @@ impl Board {
         self.cache.passive_control = None;
         self.cache.pinned_set = None;
         self.cache.move_constraints = None;
+        self.cache.checkers = None;
+        self.cache.passive_pinned = None;
+        self.cache.discoveries = None;
     }
 }
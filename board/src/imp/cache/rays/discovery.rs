@@ -4,6 +4,23 @@ use crate::{Board, ChessBoard};
 use crate::imp::cache::rays::RaySet;
 
 impl Board {
+    pub fn discovered_check_candidates_impl(&mut self) -> BitBoard {
+        self.discoveries_impl().ray_points
+    }
+
+    /// Return the ray between an active slider and the passive king which
+    /// the active piece on the given square is the only blocker of.
+    pub fn discovery_ray_impl(&mut self, location: Square) -> Option<BitBoard> {
+        self.discoveries_impl().ray(location)
+    }
+
+    fn discoveries_impl(&mut self) -> &RaySet {
+        if self.cache.discoveries.is_none() {
+            self.cache.discoveries = Some(self.compute_discoveries());
+        }
+        self.cache.discoveries.as_ref().unwrap()
+    }
+
     pub fn compute_discoveries(&self) -> RaySet {
         let locs = |side: Side| self.side(side);
         let (active, passive) = (locs(self.active), locs(self.active.reflect()));
@@ -44,12 +61,16 @@ mod test {
     use super::*;
 
     fn execute_test(fen: &'static str, expected_discoveries: RaySet) {
-        let board = fen.parse::<Board>().unwrap();
+        let mut board = fen.parse::<Board>().unwrap();
         assert_eq!(
             expected_discoveries.reflect(),
             board.reflect().compute_discoveries()
         );
         assert_eq!(expected_discoveries, board.compute_discoveries());
+        assert_eq!(
+            expected_discoveries.ray_points,
+            board.discovered_check_candidates()
+        );
     }
 
     #[test]
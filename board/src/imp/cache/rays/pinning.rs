@@ -11,23 +11,40 @@ impl Board {
         match &self.cache.pinned_set {
             Some(x) => x.clone(),
             None => {
-                let result = self.compute_pinned();
+                let result = self.compute_pinned(self.active);
                 self.cache.pinned_set = Some(result.clone());
                 result
             }
         }
     }
 
-    /// Computes the set of all active pieces which are pinned to the king,
-    /// i.e have their movement areas constrained so that they do not move
-    /// and leave the king in check.
-    fn compute_pinned(&self) -> RaySet {
+    pub fn pinned_pieces_impl(&mut self, side: Side) -> BitBoard {
+        if side == self.active {
+            return match &self.cache.pinned_set {
+                Some(x) => x.ray_points,
+                None => self.pinned_set_impl().ray_points,
+            };
+        }
+        match &self.cache.passive_pinned {
+            Some(x) => *x,
+            None => {
+                let result = self.compute_pinned(side).ray_points;
+                self.cache.passive_pinned = Some(result);
+                result
+            }
+        }
+    }
+
+    /// Computes the set of all pieces on the given side which are pinned
+    /// to their king, i.e have their movement areas constrained so that
+    /// they do not move and leave the king in check.
+    fn compute_pinned(&self, side: Side) -> RaySet {
         let locs = |side: Side| self.side(side);
-        let (active, passive) = (locs(self.active), locs(self.active.reflect()));
-        let king_loc = self.pieces.king_location(self.active);
+        let (active, passive) = (locs(side), locs(side.reflect()));
+        let king_loc = self.pieces.king_location(side);
         let mut constraint_areas: Vec<(Square, BitBoard)> = Vec::with_capacity(2);
         let mut pinned_locations = BitBoard::EMPTY;
-        for potential_pinner in self.compute_potential_pinners(king_loc) {
+        for potential_pinner in self.compute_potential_pinners(side, king_loc) {
             let cord = BitBoard::cord(king_loc, potential_pinner);
             if (cord & active).size() == 2 && (cord & passive).size() == 1 {
                 let pinned_loc = ((cord & active) - king_loc).into_iter().next().unwrap();
@@ -41,8 +58,8 @@ impl Board {
         }
     }
 
-    fn compute_potential_pinners(&self, king_loc: Square) -> BitBoard {
-        let passive_sliders = match self.active {
+    fn compute_potential_pinners(&self, side: Side, king_loc: Square) -> BitBoard {
+        let passive_sliders = match side {
             Side::White => BLACK_SLIDERS,
             Side::Black => WHITE_SLIDERS,
         };
@@ -61,9 +78,21 @@ mod test {
     use super::*;
 
     fn execute_test(fen: &'static str, expected_pinned: RaySet) {
-        let board = fen.parse::<Board>().unwrap();
-        assert_eq!(expected_pinned.reflect(), board.reflect().compute_pinned());
-        assert_eq!(expected_pinned, board.compute_pinned());
+        let mut board = fen.parse::<Board>().unwrap();
+        let mut reflected = board.reflect();
+        assert_eq!(
+            expected_pinned.reflect(),
+            reflected.compute_pinned(reflected.active)
+        );
+        assert_eq!(expected_pinned, board.compute_pinned(board.active));
+        assert_eq!(
+            expected_pinned.ray_points,
+            board.pinned_pieces(board.active)
+        );
+        assert_eq!(
+            expected_pinned.ray_points.reflect(),
+            reflected.pinned_pieces(reflected.active)
+        );
     }
 
     #[test]
@@ -79,4 +108,16 @@ mod test {
         };
         execute_test(fen, expected_pinned);
     }
+
+    #[test]
+    fn passive_side() {
+        let mut board = "4k3/4r3/8/8/8/8/4B3/4K3 b - - 0 1"
+            .parse::<Board>()
+            .unwrap();
+        assert_eq!(E2, board.pinned_pieces(Side::White));
+        assert_eq!(BitBoard::EMPTY, board.pinned_pieces(Side::Black));
+        let mut reflected = board.reflect();
+        assert_eq!(E7, reflected.pinned_pieces(Side::Black));
+        assert_eq!(BitBoard::EMPTY, reflected.pinned_pieces(Side::White));
+    }
 }
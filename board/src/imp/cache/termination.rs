@@ -2,9 +2,13 @@ use myopic_core::*;
 
 use crate::Board;
 use crate::ChessBoard;
+use crate::DrawReason;
 use crate::MoveComputeType;
 use crate::Termination;
 
+/// The set of light coloured squares, h1 is light.
+const LIGHT_SQUARES: BitBoard = BitBoard(0xAA55AA55AA55AA55);
+
 impl Board {
     pub fn termination_status_impl(&mut self) -> Option<Termination> {
         match &self.cache.termination_status {
@@ -18,9 +22,93 @@ impl Board {
     }
 
     fn compute_termination(&mut self) -> Option<Termination> {
-        if self.half_move_clock() >= 50 || self.history.has_three_repetitions() {
-            return Some(Termination::Draw);
+        if self.has_insufficient_material() {
+            return Some(Termination::Draw(DrawReason::InsufficientMaterial));
+        }
+        self.no_moves_termination()
+            .or_else(|| self.draw_rule_termination())
+    }
+
+    /// Checks whether checkmate is impossible by any sequence of legal
+    /// moves because of the material remaining. This is the case if the
+    /// only pieces besides the kings are a single knight or bishop, or any
+    /// number of bishops which all stand on squares of the same colour.
+    fn has_insufficient_material(&self) -> bool {
+        let heavy_or_pawns = self.locs(&[
+            Piece::WP,
+            Piece::BP,
+            Piece::WR,
+            Piece::BR,
+            Piece::WQ,
+            Piece::BQ,
+        ]);
+        if heavy_or_pawns.is_populated() {
+            return false;
+        }
+        let knights = self.locs(&[Piece::WN, Piece::BN]);
+        let bishops = self.locs(&[Piece::WB, Piece::BB]);
+        (knights | bishops).size() <= 1
+            || (knights.is_empty()
+                && ((bishops & LIGHT_SQUARES).is_empty() || (bishops - LIGHT_SQUARES).is_empty()))
+    }
+
+    /// Checks the repetition and move count rules, the automatic rules
+    /// are reported in preference to the claimable ones.
+    fn draw_rule_termination(&self) -> Option<Termination> {
+        let clock = self.half_move_clock();
+        let occurrences = self.occurrences();
+        let reason = if occurrences >= 5 {
+            DrawReason::FivefoldRepetition
+        } else if clock >= 150 {
+            DrawReason::SeventyFiveMoveRule
+        } else if occurrences >= 3 {
+            DrawReason::ThreefoldRepetition
+        } else if clock >= 100 {
+            DrawReason::FiftyMoveRule
+        } else {
+            return None;
+        };
+        Some(Termination::Draw(reason))
+    }
+
+    /// Count the occurrences of the current position including itself. No
+    /// position before the last capture or pawn move can recur and positions
+    /// with the other side to move cannot match so only every other position
+    /// in the reversible part of the game is examined. Positions before a
+    /// null move are not part of the same game and so are never counted.
+    fn occurrences(&self) -> usize {
+        let current = self.hash();
+        let reversible = self.half_move_clock().min(self.history.moves_since_null());
+        let matches = (2..=reversible)
+            .step_by(2)
+            .map_while(|distance| self.history.position(distance))
+            .filter(|&(hash, enpassant)| self.without_dead_enpassant(hash, enpassant) == current)
+            .count();
+        1 + matches
+    }
+
+    /// Positions only differ by their enpassant square if a capture could
+    /// actually be made. The pawn structure cannot have changed since a
+    /// previous position in the reversible part of the game and it had the
+    /// same side to move so we can check for capturing pawns on this board.
+    fn without_dead_enpassant(&self, hash: u64, enpassant: Option<Square>) -> u64 {
+        match enpassant {
+            None => hash,
+            Some(square) => {
+                let active = self.active;
+                let capturers =
+                    Piece::pawn(active.reflect()).control(square, BitBoard::EMPTY, BitBoard::EMPTY)
+                        & self.locs(&[Piece::pawn(active)]);
+                if capturers.is_empty() {
+                    hash ^ crate::hash::enpassant(square)
+                } else {
+                    hash
+                }
+            }
         }
+    }
+
+    fn no_moves_termination(&mut self) -> Option<Termination> {
         let active = self.active;
         let active_king = self.king(active);
         let passive_control = self.passive_control_impl();
@@ -77,7 +165,7 @@ impl Board {
             }
         }
         // Stalemate
-        return Some(Termination::Draw);
+        return Some(Termination::Draw(DrawReason::Stalemate));
     }
 }
 
@@ -90,6 +178,8 @@ fn qrbnp<'a>(side: Side) -> &'a [Piece] {
 
 #[cfg(test)]
 mod test {
+    use anyhow::Result;
+
     use myopic_core::Reflectable;
 
     use super::*;
@@ -130,8 +220,154 @@ mod test {
     #[test]
     fn stalemate() {
         test(
-            Some(Termination::Draw),
+            Some(Termination::Draw(DrawReason::Stalemate)),
             "6k1/6p1/7p/8/1p6/p1qp4/8/3K4 w - - 0 45",
         );
     }
+
+    #[test]
+    fn insufficient_material() {
+        let draw = Some(Termination::Draw(DrawReason::InsufficientMaterial));
+        test(draw, "8/8/3k4/8/8/4K3/8/8 w - - 0 50");
+        test(draw, "8/8/3k4/8/8/4K3/5B2/8 b - - 0 50");
+        test(draw, "8/8/3k4/6n1/8/4K3/8/8 w - - 0 50");
+        test(draw, "8/1b6/3k4/8/8/4K3/4B3/5B2 w - - 0 50");
+        test(draw, "8/8/3k4/8/2b5/4K3/4B3/8 b - - 37 50");
+    }
+
+    #[test]
+    fn sufficient_material() {
+        test(None, "8/8/3k4/8/8/4K3/4B3/4B3 w - - 0 50");
+        test(None, "8/8/3k4/8/8/4K3/5B2/6N1 w - - 0 50");
+        test(None, "8/8/3k4/8/8/4K3/8/5NN1 w - - 0 50");
+        test(None, "8/2n5/3k4/8/8/4K3/5B2/8 w - - 0 50");
+        test(None, "8/8/3k4/8/8/4K3/P7/8 w - - 0 50");
+        test(None, "8/8/3k4/8/8/4K3/8/7r b - - 0 50");
+    }
+
+    #[test]
+    fn fifty_move_rule() {
+        let fen = "4k3/8/8/8/8/8/1R6/4K3 b - - {} 80";
+        test(None, fen.replace("{}", "99").as_str());
+        test(
+            Some(Termination::Draw(DrawReason::FiftyMoveRule)),
+            fen.replace("{}", "100").as_str(),
+        );
+        test(
+            Some(Termination::Draw(DrawReason::FiftyMoveRule)),
+            fen.replace("{}", "149").as_str(),
+        );
+        test(
+            Some(Termination::Draw(DrawReason::SeventyFiveMoveRule)),
+            fen.replace("{}", "150").as_str(),
+        );
+    }
+
+    #[test]
+    fn checkmate_takes_precedence_over_move_rules() {
+        test(
+            Some(Termination::Loss),
+            "5R1k/pp2R2p/8/1b2r3/3p3q/8/PPB3P1/6K1 b - - 150 36",
+        )
+    }
+
+    fn repetition_test(fen: &str, moves: &str, expected: &[Option<Termination>]) -> Result<()> {
+        let mut board = fen.parse::<Board>()?;
+        let mut reflected = board.reflect();
+        assert_eq!(moves.split_whitespace().count(), expected.len());
+        for (mv, &expected) in moves.split_whitespace().zip(expected.iter()) {
+            board.play_uci(mv)?;
+            reflected.play_uci(&mv.chars().map(reflect_char).collect::<String>())?;
+            assert_eq!(expected, board.termination_status(), "{}", mv);
+            assert_eq!(expected, reflected.termination_status(), "{}", mv);
+        }
+        Ok(())
+    }
+
+    fn reflect_char(c: char) -> char {
+        match c {
+            '1'..='8' => (b'9' - (c as u8 - b'0')) as char,
+            c => c,
+        }
+    }
+
+    #[test]
+    fn threefold_and_fivefold_repetition() -> Result<()> {
+        let draw = |reason| Some(Termination::Draw(reason));
+        let (three, five) = (
+            DrawReason::ThreefoldRepetition,
+            DrawReason::FivefoldRepetition,
+        );
+        let shuffle = "g8f6 g1f3 f6g8 f3g1";
+        repetition_test(
+            crate::STARTPOS_FEN,
+            format!("e2e4 {} {} {} {}", shuffle, shuffle, shuffle, shuffle).as_str(),
+            &[vec![None; 8], vec![draw(three); 8], vec![draw(five); 1]].concat(),
+        )
+    }
+
+    #[test]
+    fn repetition_requires_same_side_to_move() -> Result<()> {
+        // The rook loses a tempo so the start placement recurs with black to move
+        repetition_test(
+            "4k3/8/8/8/8/8/8/R3K3 w - - 0 1",
+            "a1a2 e8d8 a2a3 d8e8 a3a1 e8d8 a1a2 d8e8 a2a3 e8d8 a3a1 d8e8 a1a2 e8d8 a2a1 d8e8",
+            &[
+                vec![None; 15],
+                vec![Some(Termination::Draw(DrawReason::ThreefoldRepetition))],
+            ]
+            .concat(),
+        )
+    }
+
+    #[test]
+    fn repetition_ignores_positions_before_null_move() -> Result<()> {
+        let mut board = crate::start();
+        board.play_uci("g1f3 g8f6 f3g1 f6g8")?;
+        board.make_null()?;
+        board.play_uci("g8f6 g1f3 f6g8 f3g1")?;
+        assert_eq!(None, board.termination_status());
+        board.make_null()?;
+        assert_eq!(None, board.termination_status());
+        board.play_uci("g1f3 g8f6 f3g1 f6g8")?;
+        assert_eq!(None, board.termination_status());
+        board.play_uci("g1f3 g8f6 f3g1 f6g8")?;
+        assert_eq!(
+            Some(Termination::Draw(DrawReason::ThreefoldRepetition)),
+            board.termination_status()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn enpassant_only_distinguishes_positions_with_a_capture() -> Result<()> {
+        let shuffle = "g8f6 g1f3 f6g8 f3g1";
+        let three = Some(Termination::Draw(DrawReason::ThreefoldRepetition));
+        // No black pawn can capture on e3 so the position after e2e4 recurs
+        repetition_test(
+            crate::STARTPOS_FEN,
+            format!("e2e4 {} {}", shuffle, shuffle).as_str(),
+            &[vec![None; 8], vec![three]].concat(),
+        )?;
+        // Here the d4 pawn could capture on c3 so the first position is distinct
+        repetition_test(
+            crate::STARTPOS_FEN,
+            format!("e2e4 d7d5 e4e5 d5d4 c2c4 {} {}", shuffle, shuffle).as_str(),
+            &[None; 13],
+        )
+    }
+
+    #[test]
+    fn repetition_broken_by_irreversible_move() -> Result<()> {
+        let shuffle = "g1f3 g8f6 f3g1 f6g8";
+        repetition_test(
+            crate::STARTPOS_FEN,
+            format!("{} e2e4 e7e5 {} {}", shuffle, shuffle, shuffle).as_str(),
+            &[
+                vec![None; 13],
+                vec![Some(Termination::Draw(DrawReason::ThreefoldRepetition))],
+            ]
+            .concat(),
+        )
+    }
 }
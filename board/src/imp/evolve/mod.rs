@@ -41,7 +41,7 @@ impl Board {
                 capture,
                 ..
             } => self.evolve_s(moving, from, dest, capture),
-            Castle { zone, .. } => self.evolve_c(zone),
+            Castle { zone, castling, .. } => self.evolve_c(zone, castling),
             Enpassant {
                 side,
                 from,
@@ -64,13 +64,31 @@ impl Board {
         Ok(())
     }
 
+    /// Public API for passing the turn, there is no move to check against
+    /// this position but a side in check cannot pass.
+    pub(super) fn evolve_null_impl(&mut self) -> Result<()> {
+        if self.in_check() {
+            return Err(anyhow!("Cannot make null move in check: {}", self.to_fen()));
+        }
+        self.history.push_null(Discards {
+            rights: self.rights,
+            enpassant: self.enpassant(),
+            half_move_clock: self.half_move_clock(),
+        });
+        self.enpassant = None;
+        self.clock += 1;
+        self.active = self.active.reflect();
+        self.clear_cache();
+        Ok(())
+    }
+
     fn evolve_s(&mut self, moving: Piece, source: Square, target: Square, captured: Option<Piece>) {
         self.pieces.toggle_piece(moving, &[source, target]);
         match captured {
             None => {}
             Some(p) => self.pieces.toggle_piece(p, &[target]),
         };
-        self.rights = self.rights.remove_rights(source | target);
+        self.rights = self.rights.remove_rights(source | target, self.castling);
         self.enpassant = Board::compute_enpassant(source, target, moving);
         self.clock = if captured.is_some() || moving.is_pawn() {
             0
@@ -79,9 +97,9 @@ impl Board {
         };
     }
 
-    fn evolve_c(&mut self, zone: CastleZone) {
+    fn evolve_c(&mut self, zone: CastleZone, castling: Castling) {
         self.rights = self.rights.apply_castling(zone.side());
-        self.toggle_castle_pieces(zone);
+        self.toggle_castle_pieces(zone, castling);
         self.enpassant = None;
         self.clock += 1;
     }
@@ -132,7 +150,7 @@ impl Board {
                 capture,
                 ..
             } => self.devolve_e(side, from, dest, capture),
-            &Castle { zone, .. } => self.devolve_c(zone),
+            &Castle { zone, castling, .. } => self.devolve_c(zone, castling),
         };
 
         self.rights = state.rights;
@@ -143,6 +161,16 @@ impl Board {
         Ok(mv)
     }
 
+    /// Public API for reversing a null move.
+    pub(super) fn devolve_null_impl(&mut self) -> Result<()> {
+        let state = self.history.attempt_pop_null()?;
+        self.clock = state.half_move_clock;
+        self.enpassant = state.enpassant;
+        self.active = self.active.reflect();
+        self.clear_cache();
+        Ok(())
+    }
+
     fn devolve_s(&mut self, piece: Piece, source: Square, target: Square, captured: Option<Piece>) {
         self.pieces.toggle_piece(piece, &[target, source]);
         match captured {
@@ -151,8 +179,8 @@ impl Board {
         };
     }
 
-    fn devolve_c(&mut self, zone: CastleZone) {
-        self.toggle_castle_pieces(zone);
+    fn devolve_c(&mut self, zone: CastleZone, castling: Castling) {
+        self.toggle_castle_pieces(zone, castling);
     }
 
     fn devolve_e(&mut self, side: Side, from: Square, dest: Square, capture: Square) {
@@ -169,9 +197,9 @@ impl Board {
         };
     }
 
-    fn toggle_castle_pieces(&mut self, zone: CastleZone) {
-        let (rook, r_source, r_target) = zone.rook_data();
-        let (king, k_source, k_target) = zone.king_data();
+    fn toggle_castle_pieces(&mut self, zone: CastleZone, castling: Castling) {
+        let (rook, r_source, r_target) = castling.rook_data(zone);
+        let (king, k_source, k_target) = castling.king_data(zone);
         self.pieces.toggle_piece(rook, &[r_source, r_target]);
         self.pieces.toggle_piece(king, &[k_source, k_target]);
     }
@@ -188,6 +216,67 @@ impl Board {
             .toggle_piece(Piece::pawn(side.reflect()), &[capture]);
     }
 
+    /// Compute the hash of the position which would follow the given move
+    /// without making it, each component is updated exactly as it would
+    /// be by the evolution of the board.
+    pub(super) fn hash_after_impl(&self, mv: &Move) -> u64 {
+        let toggle = |piece: Piece, squares: &[Square]| {
+            squares
+                .iter()
+                .fold(0u64, |h, &sq| h ^ hash::piece(piece, sq))
+        };
+        let capture_hash =
+            |capture: Option<Piece>, dest: Square| capture.map_or(0u64, |p| hash::piece(p, dest));
+        let (pieces, rights, enpassant) = match *mv {
+            Standard {
+                moving,
+                from,
+                dest,
+                capture,
+                ..
+            } => (
+                toggle(moving, &[from, dest]) ^ capture_hash(capture, dest),
+                self.rights.remove_rights(from | dest, self.castling),
+                Board::compute_enpassant(from, dest, moving),
+            ),
+            Castle { zone, castling, .. } => {
+                let (rook, r_source, r_target) = castling.rook_data(zone);
+                let (king, k_source, k_target) = castling.king_data(zone);
+                (
+                    toggle(rook, &[r_source, r_target]) ^ toggle(king, &[k_source, k_target]),
+                    self.rights.apply_castling(zone.side()),
+                    None,
+                )
+            }
+            Enpassant {
+                side,
+                from,
+                dest,
+                capture,
+                ..
+            } => (
+                toggle(Piece::pawn(side), &[from, dest])
+                    ^ toggle(Piece::pawn(side.reflect()), &[capture]),
+                self.rights,
+                None,
+            ),
+            Promotion {
+                from,
+                dest,
+                promoted,
+                capture,
+                ..
+            } => (
+                toggle(Piece::pawn(promoted.side()), &[from])
+                    ^ toggle(promoted, &[dest])
+                    ^ capture_hash(capture, dest),
+                self.rights,
+                None,
+            ),
+        };
+        super::hash(&self.pieces, rights, self.active.reflect(), enpassant) ^ pieces
+    }
+
     /// Determines the enpassant square for the next board state given a
     /// piece which has just moved from the source to the target.
     fn compute_enpassant(source: Square, target: Square, piece: Piece) -> Option<Square> {
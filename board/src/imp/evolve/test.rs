@@ -3,7 +3,7 @@ use anyhow::Result;
 use myopic_core::*;
 use myopic_core::constants::*;
 
-use crate::{ChessBoard, Move};
+use crate::{ChessBoard, Move, MoveComputeType};
 use crate::enumset::EnumSet;
 use crate::imp::Board;
 use crate::imp::test::TestBoard;
@@ -20,6 +20,7 @@ fn check_case(test_case: TestCase) -> Result<()> {
     let end = Board::from(test_case.end.clone());
     let action = Move::from(test_case.mv, start.hash())?;
 
+    assert_eq!(end.hash(), start.hash_after(&action));
     let mut forward_subject = start.clone();
     forward_subject.make(action)?;
     check_constrained_board_equality(end, forward_subject.clone());
@@ -37,6 +38,8 @@ fn check_constrained_board_equality(left: Board, right: Board) {
     assert_eq!(left.position_count(), right.position_count());
     assert_eq!(left.half_move_clock(), right.half_move_clock());
     assert_eq!(left.hash(), right.hash());
+    assert_eq!(left.pawn_hash(), right.pawn_hash());
+    assert_eq!(left.material_key(), right.material_key());
 }
 
 const EMPTY: BitBoard = BitBoard::EMPTY;
@@ -746,3 +749,93 @@ fn test_black_promotion() -> Result<()> {
         },
     })
 }
+
+#[test]
+fn test_null_move() -> Result<()> {
+    let start = "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3".parse::<Board>()?;
+    let mut board = start.clone();
+    board.make_null()?;
+    let passed = "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR b KQkq - 1 3".parse::<Board>()?;
+    check_constrained_board_equality(passed.clone(), board.clone());
+    // Check the cache was cleared by computing moves for the new active side
+    assert_eq!(
+        passed.clone().compute_moves(MoveComputeType::All),
+        board.compute_moves(MoveComputeType::All)
+    );
+    assert!(board.unmake().is_err());
+    board.unmake_null()?;
+    check_constrained_board_equality(start.clone(), board.clone());
+    assert!(board.unmake_null().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_null_move_in_check_is_error() -> Result<()> {
+    let mut board = "4k3/8/8/8/8/8/8/4K2r w - - 0 30".parse::<Board>()?;
+    assert!(board.make_null().is_err());
+    assert_eq!(board, "4k3/8/8/8/8/8/8/4K2r w - - 0 30".parse::<Board>()?);
+    Ok(())
+}
+
+#[test]
+fn test_null_move_then_move() -> Result<()> {
+    let mut board = crate::start();
+    board.play_uci("e2e4")?;
+    board.make_null()?;
+    board.play_uci("d2d4")?;
+    assert_eq!(
+        "rnbqkbnr/pppppppp/8/8/3PP3/8/PPP2PPP/RNBQKBNR b KQkq d3 0 2",
+        board.to_fen()
+    );
+    assert!(board.unmake_null().is_err());
+    board.unmake()?;
+    board.unmake_null()?;
+    board.unmake()?;
+    assert_eq!(crate::start(), board);
+    Ok(())
+}
+
+#[test]
+fn test_pawn_hash_and_material_key() -> Result<()> {
+    let mut board = crate::start();
+    let (pawn_hash, material_key) = (board.pawn_hash(), board.material_key());
+    board.play_uci("g1f3 b8c6")?;
+    assert_eq!(pawn_hash, board.pawn_hash());
+    assert_eq!(material_key, board.material_key());
+    board.play_uci("e2e4 d7d5 e4d5 d8d5 b1c3 d5a2 a1a2")?;
+    let expected = board.to_fen().parse::<Board>()?;
+    assert_ne!(pawn_hash, board.pawn_hash());
+    assert_eq!(expected.pawn_hash(), board.pawn_hash());
+    assert_eq!(expected.material_key(), board.material_key());
+    let count = |key: u64, p: Piece| (key >> (4 * p as usize)) & 0b1111;
+    assert_eq!(6, count(board.material_key(), Piece::WP));
+    assert_eq!(7, count(board.material_key(), Piece::BP));
+    assert_eq!(0, count(board.material_key(), Piece::BQ));
+    for _ in 0..9 {
+        board.unmake()?;
+    }
+    assert_eq!(pawn_hash, board.pawn_hash());
+    assert_eq!(material_key, board.material_key());
+    Ok(())
+}
+
+#[test]
+fn test_hash_after() -> Result<()> {
+    for fen in &[
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1",
+    ] {
+        let board = fen.parse::<Board>()?;
+        for mut board in vec![board.reflect(), board] {
+            for mv in board.compute_moves(MoveComputeType::All) {
+                let predicted = board.hash_after(&mv);
+                board.make(mv.clone())?;
+                assert_eq!(board.hash(), predicted, "{} in {}", mv, fen);
+                board.unmake()?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -58,10 +58,17 @@ fn to_fen_side<B: ChessBoard>(board: &B) -> String {
 }
 
 fn to_fen_castling_rights<B: ChessBoard>(board: &B) -> String {
+    let castling = board.castling();
     let rights = board
         .remaining_rights()
         .iter()
-        .map(castlezone_to_fen)
+        .map(|zone| {
+            if castling.is_chess960() {
+                castlezone_to_shredder_fen(zone, castling)
+            } else {
+                castlezone_to_fen(zone).to_owned()
+            }
+        })
         .collect::<String>();
     if rights.is_empty() {
         format!("-")
@@ -94,6 +101,16 @@ fn castlezone_to_fen(zone: CastleZone) -> &'static str {
     }
 }
 
+/// Chess960 rights are written in Shredder-FEN using the file of the rook.
+fn castlezone_to_shredder_fen(zone: CastleZone, castling: Castling) -> String {
+    let (_, rook_src, _) = castling.rook_data(zone);
+    let file = format!("{}", rook_src).remove(0);
+    match zone.side() {
+        Side::White => file.to_ascii_uppercase().to_string(),
+        Side::Black => file.to_ascii_lowercase().to_string(),
+    }
+}
+
 fn piece_to_fen(piece: Piece) -> &'static str {
     match piece {
         Piece::WP => "P",
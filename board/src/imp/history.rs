@@ -1,13 +1,10 @@
 use anyhow::{anyhow, Result};
-use itertools::Itertools;
 
 use myopic_core::{Reflectable, Square};
 
 use crate::Move;
 use crate::imp::rights::Rights;
 
-const REPETITION_WINDOW: usize = 15;
-
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Discards {
     pub rights: Rights,
@@ -22,8 +19,8 @@ pub struct History {
     prev_position_count: usize,
     /// The stack which tracks the moves and positional
     /// information which gets lost when you make/unmake
-    /// moves
-    inner: Vec<(Move, Discards)>,
+    /// moves, null moves are recorded without a move
+    inner: Vec<(Option<Move>, Discards)>,
 }
 
 impl History {
@@ -39,37 +36,57 @@ impl History {
     }
 
     pub fn push(&mut self, mv: Move, discards: Discards) {
-        self.inner.push((mv, discards));
+        self.inner.push((Some(mv), discards));
     }
 
-    pub fn has_three_repetitions(&self) -> bool {
-        self.inner.len() >= REPETITION_WINDOW && {
-            let hashes = self
-                .inner
-                .iter()
-                .map(|(m, _)| m.source())
-                .sorted()
-                .collect_vec();
-            let (mut last, mut count) = (hashes[0], 1);
-            for &hash in hashes.iter().skip(1) {
-                if hash == last {
-                    count += 1;
-                    if count == 3 {
-                        break;
-                    }
-                } else {
-                    count = 1;
-                    last = hash;
-                }
+    pub fn push_null(&mut self, discards: Discards) {
+        self.inner.push((None, discards));
+    }
+
+    /// Return the number of moves made since the most recent null move
+    /// or since we started making moves if there is no null move.
+    pub fn moves_since_null(&self) -> usize {
+        self.inner.iter().rev().take_while(|(m, _)| m.is_some()).count()
+    }
+
+    /// Return the hash and enpassant square of the position which occurred
+    /// the given number of moves before the current one if it exists and
+    /// was not followed by a null move.
+    pub fn position(&self, distance: usize) -> Option<(u64, Option<Square>)> {
+        let n = self.inner.len();
+        if 0 < distance && distance <= n {
+            match &self.inner[n - distance] {
+                (Some(mv), discards) => Some((mv.source(), discards.enpassant)),
+                (None, _) => None,
             }
-            count == 3
+        } else {
+            None
         }
     }
 
+    /// Iterate over the moves made on the board in the order they were
+    /// made, null moves are given as None.
+    pub fn moves(&self) -> impl DoubleEndedIterator<Item = Option<&Move>> {
+        self.inner.iter().map(|(m, _)| m.as_ref())
+    }
+
     pub fn attempt_pop(&mut self) -> Result<(Move, Discards)> {
-        self.inner
-            .pop()
-            .ok_or(anyhow!("Empty history, could not pop last move!"))
+        match self.inner.last() {
+            None => Err(anyhow!("Empty history, could not pop last move!")),
+            Some((None, _)) => Err(anyhow!("Last move was a null move, could not pop it!")),
+            Some((Some(_), _)) => {
+                let (mv, discards) = self.inner.pop().unwrap();
+                Ok((mv.unwrap(), discards))
+            }
+        }
+    }
+
+    pub fn attempt_pop_null(&mut self) -> Result<Discards> {
+        match self.inner.last() {
+            None => Err(anyhow!("Empty history, could not pop last null move!")),
+            Some((Some(mv), _)) => Err(anyhow!("Last move {} was not a null move!", mv)),
+            Some((None, _)) => Ok(self.inner.pop().unwrap().1),
+        }
     }
 
     pub(crate) fn reflect_for(&self, new_hash: u64) -> History {
@@ -80,7 +97,7 @@ impl History {
                 .iter()
                 .map(|(m, d)| {
                     (
-                        m.reflect_for(new_hash),
+                        m.as_ref().map(|m| m.reflect_for(new_hash)),
                         Discards {
                             rights: d.rights.reflect(),
                             enpassant: d.enpassant.reflect(),
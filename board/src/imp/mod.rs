@@ -13,6 +13,7 @@ use crate::imp::rights::Rights;
 use crate::imp::history::History;
 use crate::imp::positions::Positions;
 use crate::MoveComputeType;
+use crate::MoveList;
 use crate::mv::{Move, parse_op};
 use crate::parse::patterns;
 use crate::Termination;
@@ -24,14 +25,20 @@ mod fen;
 mod history;
 mod moves;
 mod positions;
+#[cfg(feature = "serde")]
+mod serialization;
+mod validation;
 #[cfg(test)]
 mod test;
 
+pub use validation::FenError;
+
 #[derive(Debug, Clone)]
 pub struct Board {
     history: History,
     pieces: Positions,
     rights: Rights,
+    castling: Castling,
     active: Side,
     enpassant: Option<Square>,
     clock: usize,
@@ -42,6 +49,20 @@ impl FromStr for Board {
     type Err = Error;
 
     fn from_str(fen: &str) -> Result<Self, Self::Err> {
+        Board::parse_fen(fen, false)
+    }
+}
+
+impl Board {
+    /// Parse a board from a FEN string describing a Chess960 game. The
+    /// castling rights may be given in either the X-FEN or Shredder-FEN
+    /// form and castling moves will use the king takes rook uci notation
+    /// even if the kings and rooks start on their standard squares.
+    pub fn from_fen_chess960(fen: &str) -> Result<Board> {
+        Board::parse_fen(fen, true)
+    }
+
+    fn parse_fen(fen: &str, chess960: bool) -> Result<Board> {
         if patterns::fen().is_match(&fen) {
             let space_split: Vec<_> = patterns::space()
                 .split(&fen)
@@ -49,10 +70,13 @@ impl FromStr for Board {
                 .collect();
             let active = space_split[1].parse::<Side>()?;
             let curr_move = space_split[5].parse::<usize>()?;
+            let pieces = space_split[0].parse::<Positions>()?;
+            let (rights, castling) = rights::parse(&space_split[2], &pieces, chess960)?;
             Ok(Board {
-                pieces: space_split[0].parse()?,
-                active: space_split[1].parse()?,
-                rights: space_split[2].parse()?,
+                pieces,
+                active,
+                rights,
+                castling,
                 enpassant: parse_op(space_split[3].as_str())?,
                 clock: space_split[4].parse::<usize>()?,
                 history: History::new(2 * (max(curr_move, 1) - 1) + (active as usize)),
@@ -75,7 +99,7 @@ fn hash(pos: &Positions, rights: Rights, active: Side, ep: Option<Square>) -> u6
 mod fen_test {
     use anyhow::Result;
 
-    use crate::{CastleZone, constants::*, Side, Square};
+    use crate::{CastleZone, Castling, ChessBoard, constants::*, Side, Square};
     use crate::Board;
     use crate::enumset::EnumSet;
     use crate::imp::test::TestBoard;
@@ -172,6 +196,30 @@ mod fen_test {
         };
         test(board, String::from(fen))
     }
+
+    #[test]
+    fn chess960_rights() -> Result<()> {
+        let fen = "nrbqkbrn/pppppppp/8/8/8/8/PPPPPPPP/NRBQKBRN w KQkq - 0 1";
+        let board = Board::from_fen_chess960(fen)?;
+        assert_eq!(
+            "nrbqkbrn/pppppppp/8/8/8/8/PPPPPPPP/NRBQKBRN w GBgb - 0 1",
+            board.to_fen()
+        );
+        assert_eq!(board, board.to_fen().parse::<Board>()?);
+        assert_eq!(
+            Castling::chess960(Square::E1, Square::G1, Square::B1)?,
+            board.castling()
+        );
+        let standard = Board::from_fen_chess960(crate::STARTPOS_FEN)?;
+        assert_eq!(
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1",
+            standard.to_fen()
+        );
+        assert!("4k3/8/8/8/8/8/8/1R2K1R1 w Gh - 0 1"
+            .parse::<Board>()
+            .is_err());
+        Ok(())
+    }
 }
 
 // Trait implementations
@@ -187,6 +235,7 @@ impl Reflectable for Board {
             clock: self.clock,
             pieces,
             rights,
+            castling: self.castling,
             active,
             enpassant,
             cache: CalculationCache::default(),
@@ -198,6 +247,7 @@ impl PartialEq<Board> for Board {
     fn eq(&self, other: &Board) -> bool {
         self.pieces == other.pieces
             && self.rights == other.rights
+            && self.castling == other.castling
             && self.enpassant == other.enpassant
             && self.active == other.active
             && self.half_move_clock() == other.half_move_clock()
@@ -213,10 +263,59 @@ impl ChessBoard for Board {
         self.devolve_impl()
     }
 
+    fn make_null(&mut self) -> Result<()> {
+        self.evolve_null_impl()
+    }
+
+    fn unmake_null(&mut self) -> Result<()> {
+        self.devolve_null_impl()
+    }
+
     fn compute_moves(&mut self, computation_type: MoveComputeType) -> Vec<Move> {
         self.compute_moves_impl(computation_type)
     }
 
+    fn compute_moves_into(&mut self, computation_type: MoveComputeType, dest: &mut MoveList) {
+        dest.clear();
+        self.compute_moves_into_impl(computation_type, dest)
+    }
+
+    fn moves_from(&mut self, square: Square) -> Vec<Move> {
+        self.moves_from_impl(square)
+    }
+
+    fn moves_to(&mut self, square: Square) -> Vec<Move> {
+        self.moves_to_impl(square)
+    }
+
+    fn is_pseudo_legal(&self, mv: &Move) -> bool {
+        self.is_pseudo_legal_impl(mv)
+    }
+
+    fn is_legal(&mut self, mv: &Move) -> bool {
+        self.is_legal_impl(mv)
+    }
+
+    fn gives_check(&mut self, mv: &Move) -> bool {
+        self.gives_check_impl(mv)
+    }
+
+    fn attackers_to(&mut self, square: Square, side: Side) -> BitBoard {
+        self.attackers_to_impl(square, side)
+    }
+
+    fn checkers(&mut self) -> BitBoard {
+        self.checkers_impl()
+    }
+
+    fn pinned_pieces(&mut self, side: Side) -> BitBoard {
+        self.pinned_pieces_impl(side)
+    }
+
+    fn discovered_check_candidates(&mut self) -> BitBoard {
+        self.discovered_check_candidates_impl()
+    }
+
     fn termination_status(&mut self) -> Option<Termination> {
         self.termination_status_impl()
     }
@@ -243,6 +342,18 @@ impl ChessBoard for Board {
         hash(&self.pieces, self.rights, self.active, self.enpassant)
     }
 
+    fn hash_after(&self, action: &Move) -> u64 {
+        self.hash_after_impl(action)
+    }
+
+    fn pawn_hash(&self) -> u64 {
+        self.pieces.pawn_hash()
+    }
+
+    fn material_key(&self) -> u64 {
+        self.pieces.material_key()
+    }
+
     fn active(&self) -> Side {
         self.active
     }
@@ -266,6 +377,10 @@ impl ChessBoard for Board {
         self.pieces.piece_at(location)
     }
 
+    fn move_history(&self) -> Vec<Option<Move>> {
+        self.history.moves().map(|mv| mv.cloned()).collect()
+    }
+
     fn half_move_clock(&self) -> usize {
         self.clock
     }
@@ -278,6 +393,10 @@ impl ChessBoard for Board {
         self.rights.0
     }
 
+    fn castling(&self) -> Castling {
+        self.castling
+    }
+
     fn play_pgn(&mut self, moves: &str) -> Result<Vec<Move>> {
         let mut dest = vec![];
         for mv in crate::parse::pgn::moves(self, moves)? {
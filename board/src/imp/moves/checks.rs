@@ -0,0 +1,159 @@
+use myopic_core::*;
+
+use crate::ChessBoard;
+use crate::imp::Board;
+use crate::mv::Move;
+
+/// Prediction of whether a legal move gives check to the passive king
+/// without making it. Direct checks are found from the control of the
+/// moved piece on the board after the move, discovered checks from the
+/// cached discovery rays.
+impl Board {
+    pub fn gives_check_impl(&mut self, mv: &Move) -> bool {
+        let (active, passive) = (self.active, self.active.reflect());
+        let passive_king = self.king(passive);
+        let (whites, blacks) = self.sides();
+        // The occupancy after moving an active piece from one square to
+        // another, any passive piece on the destination is removed
+        let after = |from: Square, dest: Square| -> (BitBoard, BitBoard) {
+            match active {
+                Side::White => ((whites - from) | dest, blacks - dest),
+                Side::Black => (whites - dest, (blacks - from) | dest),
+            }
+        };
+        match *mv {
+            Move::Standard {
+                moving, from, dest, ..
+            } => {
+                let (w, b) = after(from, dest);
+                moving.control(dest, w, b).contains(passive_king) || self.discovers(from, dest)
+            }
+            Move::Promotion {
+                from,
+                dest,
+                promoted,
+                ..
+            } => {
+                let (w, b) = after(from, dest);
+                promoted.control(dest, w, b).contains(passive_king) || self.discovers(from, dest)
+            }
+            Move::Enpassant {
+                from,
+                dest,
+                capture,
+                ..
+            } => {
+                // Two pieces leave the line between a slider and the king
+                // so look for any slider attack on the new occupancy
+                let (w, b) = after(from, dest);
+                let (w, b) = (w - capture, b - capture);
+                let sliders = match active {
+                    Side::White => [Piece::WB, Piece::WR, Piece::WQ],
+                    Side::Black => [Piece::BB, Piece::BR, Piece::BQ],
+                };
+                Piece::pawn(active)
+                    .control(dest, w, b)
+                    .contains(passive_king)
+                    || sliders
+                        .iter()
+                        .any(|&p| self.locs(&[p]).intersects(p.control(passive_king, w, b)))
+            }
+            Move::Castle { zone, castling, .. } => {
+                let (_, king_src, king_dest) = castling.king_data(zone);
+                let (rook, rook_src, rook_dest) = castling.rook_data(zone);
+                // The king and rook may finish on each other's squares
+                let moved = |side: BitBoard| (side - king_src - rook_src) | king_dest | rook_dest;
+                let (w, b) = match active {
+                    Side::White => (moved(whites), blacks),
+                    Side::Black => (whites, moved(blacks)),
+                };
+                // In Chess960 the king leaving its square may also
+                // uncover an attack from another slider
+                let sliders = match active {
+                    Side::White => [Piece::WB, Piece::WR, Piece::WQ],
+                    Side::Black => [Piece::BB, Piece::BR, Piece::BQ],
+                };
+                rook.control(rook_dest, w, b).contains(passive_king)
+                    || sliders.iter().any(|&p| {
+                        (self.locs(&[p]) - rook_src).intersects(p.control(passive_king, w, b))
+                    })
+            }
+        }
+    }
+
+    /// A piece moving off the ray it is blocking between an active slider
+    /// and the passive king gives a discovered check.
+    fn discovers(&mut self, from: Square, dest: Square) -> bool {
+        self.discovery_ray_impl(from)
+            .map(|ray| !ray.contains(dest))
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+
+    use crate::MoveComputeType;
+
+    use super::*;
+
+    /// Check the prediction for every legal move in the position and its
+    /// reflection and that the expected number of moves give check.
+    fn execute_test(fen: &str, expected_checks: usize) -> Result<()> {
+        let board = fen.parse::<Board>()?;
+        for mut board in vec![board.reflect(), board] {
+            let mut checks = 0;
+            for mv in board.compute_moves(MoveComputeType::All) {
+                let predicted = board.gives_check(&mv);
+                board.make(mv.clone())?;
+                assert_eq!(board.in_check(), predicted, "{} in {}", mv, fen);
+                board.unmake()?;
+                checks += predicted as usize;
+            }
+            assert_eq!(expected_checks, checks, "{}", fen);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn direct_checks() -> Result<()> {
+        execute_test(
+            "r1bqkbnr/pppp1ppp/2n5/4p3/2B1P3/5Q2/PPPP1PPP/RNB1K1NR w KQkq - 2 3",
+            2,
+        )?;
+        execute_test(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            0,
+        )
+    }
+
+    #[test]
+    fn discovered_checks() -> Result<()> {
+        execute_test("4k3/8/8/8/4N3/8/8/4R1K1 w - - 0 1", 8)?;
+        execute_test("7k/8/5N2/8/3B4/8/8/K7 w - - 0 1", 8)
+    }
+
+    #[test]
+    fn promotion_checks() -> Result<()> {
+        execute_test("3k4/1P6/8/8/8/8/8/4K3 w - - 0 1", 2)?;
+        execute_test(
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+            0,
+        )
+    }
+
+    #[test]
+    fn enpassant_checks() -> Result<()> {
+        execute_test("8/8/8/1k1pP2R/8/8/8/4K3 w - d6 0 2", 1)?;
+        execute_test("8/8/8/5k2/3pP3/8/8/2B1K3 b - e3 0 2", 0)?;
+        execute_test("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", 2)
+    }
+
+    #[test]
+    fn castling_checks() -> Result<()> {
+        execute_test("5k2/8/8/8/8/8/8/4K2R w K - 0 1", 3)?;
+        execute_test("3k4/8/8/8/8/8/8/R3K3 w Q - 0 1", 3)?;
+        execute_test("5k2/8/8/8/8/8/8/1R3KR1 w GB - 0 1", 3)
+    }
+}
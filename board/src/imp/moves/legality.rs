@@ -0,0 +1,233 @@
+use myopic_core::*;
+
+use crate::ChessBoard;
+use crate::imp::Board;
+use crate::MoveComputeType;
+use crate::mv::Move;
+
+/// Validation of single moves which may not have been generated by this
+/// board, the checks mirror the conditions used by the move generation
+/// so a move is legal if and only if it would be computed.
+impl Board {
+    pub fn is_pseudo_legal_impl(&self, mv: &Move) -> bool {
+        if mv.source() != self.hash() {
+            return false;
+        }
+        let (active, (whites, blacks)) = (self.active, self.sides());
+        let active_pawn = Piece::pawn(active);
+        match *mv {
+            Move::Standard {
+                moving,
+                from,
+                dest,
+                capture,
+                ..
+            } => {
+                moving.side() == active
+                    && self.piece(from) == Some(moving)
+                    && self.piece(dest) == capture
+                    && !(moving.is_pawn() && active.pawn_promoting_dest_rank().contains(dest))
+                    && moving.moves(from, whites, blacks).contains(dest)
+            }
+            Move::Enpassant {
+                side,
+                from,
+                dest,
+                capture,
+                ..
+            } => {
+                side == active
+                    && self.enpassant == Some(dest)
+                    && self.piece(from) == Some(active_pawn)
+                    && dest.next(active.pawn_dir().reflect()) == Some(capture)
+                    && active_pawn.control(from, whites, blacks).contains(dest)
+            }
+            Move::Promotion {
+                from,
+                dest,
+                promoted,
+                capture,
+                ..
+            } => {
+                self.piece(from) == Some(active_pawn)
+                    && active.pawn_promoting_from_rank().contains(from)
+                    && Board::promotion_targets(active).contains(&promoted)
+                    && self.piece(dest) == capture
+                    && active_pawn.moves(from, whites, blacks).contains(dest)
+            }
+            Move::Castle { zone, castling, .. } => {
+                zone.side() == active
+                    && castling == self.castling
+                    && self.rights.0.contains(zone)
+                    && !(whites | blacks).intersects(castling.unoccupied_requirement(zone))
+            }
+        }
+    }
+
+    pub fn is_legal_impl(&mut self, mv: &Move) -> bool {
+        if !self.is_pseudo_legal_impl(mv) {
+            return false;
+        }
+        let constraints = self.constraints_impl(MoveComputeType::All);
+        match *mv {
+            Move::Standard { from, dest, .. } | Move::Promotion { from, dest, .. } => {
+                constraints.get(from).contains(dest)
+            }
+            Move::Enpassant { from, capture, .. } => {
+                constraints.get(from).contains(capture)
+                    && self.enpassant_doesnt_discover_attack(from)
+            }
+            Move::Castle { zone, castling, .. } => {
+                constraints
+                    .get(self.king(self.active))
+                    .subsumes(castling.uncontrolled_requirement(zone))
+                    && !self.castle_exposes_king(zone)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeSet;
+
+    use anyhow::Result;
+
+    use super::*;
+
+    /// Every move which is consistent with the pieces on the board along
+    /// with many which are not.
+    fn candidate_moves(board: &Board) -> Vec<Move> {
+        let (source, active) = (board.hash(), board.active());
+        let mut dest = vec![];
+        for from in Square::iter() {
+            for to in Square::iter().filter(|&sq| sq != from) {
+                let capture = board.piece(to);
+                if let Some(moving) = board.piece(from) {
+                    dest.push(Move::Standard {
+                        source,
+                        moving,
+                        from,
+                        dest: to,
+                        capture,
+                    });
+                }
+                for promoted in Piece::all() {
+                    dest.push(Move::Promotion {
+                        source,
+                        from,
+                        dest: to,
+                        promoted,
+                        capture,
+                    });
+                }
+                if let Some(captured) = to.next(active.pawn_dir().reflect()) {
+                    dest.push(Move::Enpassant {
+                        source,
+                        side: active,
+                        from,
+                        dest: to,
+                        capture: captured,
+                    });
+                }
+            }
+        }
+        for zone in CastleZone::iter() {
+            for &castling in &[Castling::STANDARD, board.castling()] {
+                dest.push(Move::Castle {
+                    source,
+                    zone,
+                    castling,
+                });
+            }
+        }
+        dest
+    }
+
+    fn execute_test(fen: &str) -> Result<()> {
+        let board = fen.parse::<Board>()?;
+        for mut board in vec![board.reflect(), board] {
+            let legal = board
+                .compute_moves(MoveComputeType::All)
+                .into_iter()
+                .collect::<BTreeSet<_>>();
+            for mv in candidate_moves(&board) {
+                let expected = legal.contains(&mv);
+                assert_eq!(expected, board.is_legal(&mv), "{} in {}", mv, fen);
+                if expected {
+                    assert!(board.is_pseudo_legal(&mv));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn castling_and_pins() -> Result<()> {
+        execute_test("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")
+    }
+
+    #[test]
+    fn chess960_castling() -> Result<()> {
+        execute_test("bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9")?;
+        execute_test("1rk3r1/8/8/8/8/8/8/1RK3R1 w GBgb - 0 1")?;
+        // Moving the castling rook would expose the king on c1
+        let mut board = "4k3/8/8/8/8/8/8/rRK4R w HB - 0 1".parse::<Board>()?;
+        let castles = board
+            .moves_from(Square::C1)
+            .into_iter()
+            .filter(|mv| matches!(mv, Move::Castle { .. }))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec!["c1h1"],
+            castles.iter().map(|m| m.uci_format()).collect::<Vec<_>>()
+        );
+        execute_test("4k3/8/8/8/8/8/8/rRK4R w HB - 0 1")
+    }
+
+    #[test]
+    fn promotions() -> Result<()> {
+        execute_test("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1")
+    }
+
+    #[test]
+    fn enpassant_discovered_check() -> Result<()> {
+        execute_test("8/8/8/K2pP2r/8/8/8/7k w - d6 0 2")
+    }
+
+    #[test]
+    fn in_check() -> Result<()> {
+        execute_test("r1bqkbnr/pppp1Qpp/2n5/4p3/2B1P3/8/PPPP1PPP/RNB1K1NR b KQkq - 0 4")?;
+        execute_test("4k3/8/8/1b6/8/8/3P4/r3K2R w K - 0 1")
+    }
+
+    #[test]
+    fn pinned_move_is_only_pseudo_legal() -> Result<()> {
+        let mut board = "4k3/4r3/8/8/8/8/4B3/4K3 w - - 0 1".parse::<Board>()?;
+        let pinned = Move::Standard {
+            source: board.hash(),
+            moving: Piece::WB,
+            from: Square::E2,
+            dest: Square::D3,
+            capture: None,
+        };
+        assert!(board.is_pseudo_legal(&pinned));
+        assert!(!board.is_legal(&pinned));
+        let fen = board.to_fen();
+        assert!(board.make_checked(pinned).is_err());
+        assert_eq!(fen, board.to_fen());
+        let legal = board.parse_uci("e1d1")?;
+        board.make_checked(legal)?;
+        assert_eq!(Side::Black, board.active());
+        Ok(())
+    }
+
+    #[test]
+    fn move_from_other_position_is_not_pseudo_legal() -> Result<()> {
+        let mut board = crate::start();
+        let mv = board.parse_uci("e2e4")?;
+        board.play_uci("g1f3 g8f6")?;
+        assert!(!board.is_pseudo_legal(&mv));
+        Ok(())
+    }
+}
@@ -9,68 +9,123 @@ use crate::mv::Move;
 #[cfg(test)]
 mod test;
 
+mod checks;
 mod enpassantsrc;
+mod legality;
 
 impl Board {
     pub fn compute_moves_impl(&mut self, computation_type: MoveComputeType) -> Vec<Move> {
+        let mut dest = Vec::with_capacity(60);
+        self.compute_moves_into_impl(computation_type, &mut dest);
+        dest
+    }
+
+    pub fn compute_moves_into_impl<E>(&mut self, computation_type: MoveComputeType, dest: &mut E)
+    where
+        E: Extend<Move>,
+    {
+        self.compute_moves_between(computation_type, BitBoard::ALL, BitBoard::ALL, dest)
+    }
+
+    pub fn moves_from_impl(&mut self, square: Square) -> Vec<Move> {
+        let (mut dest, origins, dests) = (vec![], square.lift(), BitBoard::ALL);
+        self.compute_moves_between(MoveComputeType::All, origins, dests, &mut dest);
+        dest
+    }
+
+    pub fn moves_to_impl(&mut self, square: Square) -> Vec<Move> {
+        let (mut dest, origins, dests) = (vec![], BitBoard::ALL, square.lift());
+        self.compute_moves_between(MoveComputeType::All, origins, dests, &mut dest);
+        dest
+    }
+
+    /// Compute the moves of the given type which start on one of the
+    /// origin squares and finish on one of the destination squares, the
+    /// squares a castling king moves between are used for castling moves.
+    fn compute_moves_between<E>(
+        &mut self,
+        computation_type: MoveComputeType,
+        origins: BitBoard,
+        dests: BitBoard,
+        dest: &mut E,
+    ) where
+        E: Extend<Move>,
+    {
         let constraints = self.constraints_impl(computation_type);
-        let pawn_moves = self.compute_pawn_moves(&constraints);
-        let nbrqk_moves = self.compute_nbrqk_moves(&constraints);
-        let castle_moves = match computation_type {
-            MoveComputeType::All => self.compute_castle_moves(&constraints),
-            _ => Vec::with_capacity(0),
+        // Captures and quiets split the moves by their destination square
+        // except for promotions which are all treated as captures
+        let targets = dests
+            & match computation_type {
+                MoveComputeType::Captures => self.side(self.active.reflect()),
+                MoveComputeType::Quiets => !self.all_pieces(),
+                _ => BitBoard::ALL,
+            };
+        let (specials, castles) = match computation_type {
+            MoveComputeType::All => (dests, true),
+            MoveComputeType::Quiets => (BitBoard::EMPTY, true),
+            _ => (dests, false),
         };
-        pawn_moves
-            .into_iter()
-            .chain(nbrqk_moves.into_iter())
-            .chain(castle_moves.into_iter())
-            .collect()
+        self.compute_pawn_moves(&constraints, origins, targets, specials, dest);
+        self.compute_nbrqk_moves(&constraints, origins, targets, dest);
+        if castles {
+            self.compute_castle_moves(&constraints, origins, dests, dest);
+        }
     }
 
-    fn compute_nbrqk_moves(&self, constraints: &MoveConstraints) -> Vec<Move> {
-        let mut dest: Vec<Move> = Vec::with_capacity(40);
+    fn compute_nbrqk_moves<E>(
+        &self,
+        constraints: &MoveConstraints,
+        origins: BitBoard,
+        targets: BitBoard,
+        dest: &mut E,
+    ) where
+        E: Extend<Move>,
+    {
         let (whites, blacks) = self.sides();
         let unchecked_moves = |p: Piece, loc: Square| p.moves(loc, whites, blacks);
         // Add standard moves for pieces which aren't pawns or king
         for piece in Piece::of(self.active).skip(1) {
-            for location in self.pieces.locs(piece) {
-                let moves = unchecked_moves(piece, location) & constraints.get(location);
+            for location in self.pieces.locs(piece) & origins {
+                let moves = unchecked_moves(piece, location) & constraints.get(location) & targets;
                 dest.extend(self.standards(piece, location, moves));
             }
         }
-        dest
     }
 
-    fn standards(&self, moving: Piece, from: Square, dests: BitBoard) -> Vec<Move> {
+    fn standards(
+        &self,
+        moving: Piece,
+        from: Square,
+        dests: BitBoard,
+    ) -> impl Iterator<Item = Move> + '_ {
         let source = self.hash();
-        dests
-            .iter()
-            .map(|dest| Move::Standard {
-                source,
-                moving,
-                from,
-                dest,
-                capture: self.piece(dest),
-            })
-            .collect()
+        dests.iter().map(move |dest| Move::Standard {
+            source,
+            moving,
+            from,
+            dest,
+            capture: self.piece(dest),
+        })
     }
 
-    fn promotions(&self, side: Side, from: Square, dests: BitBoard) -> Vec<Move> {
+    fn promotions(
+        &self,
+        side: Side,
+        from: Square,
+        dests: BitBoard,
+    ) -> impl Iterator<Item = Move> + '_ {
         let source = self.hash();
-        dests
-            .iter()
-            .flat_map(|dest| {
-                Board::promotion_targets(side)
-                    .iter()
-                    .map(move |&promoted| Move::Promotion {
-                        source,
-                        from,
-                        dest,
-                        promoted,
-                        capture: self.piece(dest),
-                    })
-            })
-            .collect()
+        dests.iter().flat_map(move |dest| {
+            Board::promotion_targets(side)
+                .iter()
+                .map(move |&promoted| Move::Promotion {
+                    source,
+                    from,
+                    dest,
+                    promoted,
+                    capture: self.piece(dest),
+                })
+        })
     }
 
     fn promotion_targets<'a>(side: Side) -> &'a [Piece; 4] {
@@ -80,39 +135,53 @@ impl Board {
         }
     }
 
-    fn compute_pawn_moves(&self, constraints: &MoveConstraints) -> Vec<Move> {
-        let mut moves: Vec<Move> = Vec::with_capacity(20);
+    /// Enpassant moves and promotions are only added if they finish on one
+    /// of the special squares, the targets only restrict the standard pawn
+    /// moves.
+    fn compute_pawn_moves<E>(
+        &self,
+        constraints: &MoveConstraints,
+        origins: BitBoard,
+        targets: BitBoard,
+        specials: BitBoard,
+        dest: &mut E,
+    ) where
+        E: Extend<Move>,
+    {
         let (standard, enpassant, promotion) = self.separate_pawn_locs();
+        let (enpassant, promotion) = (enpassant & origins, promotion & origins);
         let (active_pawn, (whites, blacks)) = (Piece::pawn(self.active), self.sides());
         let compute_moves = |loc: Square| active_pawn.moves(loc, whites, blacks);
 
         // Add moves for pawns which can only produce standard moves.
-        for location in standard | enpassant {
-            let targets = compute_moves(location) & constraints.get(location);
-            moves.extend(self.standards(active_pawn, location, targets));
+        for location in (standard | enpassant) & origins {
+            let moves = compute_moves(location) & constraints.get(location) & targets;
+            dest.extend(self.standards(active_pawn, location, moves));
+        }
+        if specials.is_empty() {
+            return;
         }
         let (source, active) = (self.hash(), self.active);
         for from in enpassant {
-            let dest = self.enpassant.unwrap();
-            let capture = dest.next(active.pawn_dir().reflect()).unwrap();
-            if constraints.get(from).contains(capture)
+            let ep_dest = self.enpassant.unwrap();
+            let capture = ep_dest.next(active.pawn_dir().reflect()).unwrap();
+            if specials.contains(ep_dest)
+                && constraints.get(from).contains(capture)
                 && self.enpassant_doesnt_discover_attack(from)
             {
-                moves.push(Move::Enpassant {
+                dest.extend(std::iter::once(Move::Enpassant {
                     source,
                     side: active,
                     from,
-                    dest,
+                    dest: ep_dest,
                     capture,
-                });
+                }));
             }
         }
         for location in promotion {
-            let targets = compute_moves(location) & constraints.get(location);
-            moves.extend(self.promotions(self.active, location, targets));
+            let moves = compute_moves(location) & constraints.get(location) & specials;
+            dest.extend(self.promotions(self.active, location, moves));
         }
-
-        moves
     }
 
     fn enpassant_doesnt_discover_attack(&self, enpassant_source: Square) -> bool {
@@ -138,9 +207,9 @@ impl Board {
     }
 
     fn separate_pawn_locs(&self) -> (BitBoard, BitBoard, BitBoard) {
-        let enpassant_source = self.enpassant.map_or(BitBoard::EMPTY, |sq| {
-            enpassantsrc::squares(self.active, sq)
-        });
+        let enpassant_source = self
+            .enpassant
+            .map_or(BitBoard::EMPTY, |sq| enpassantsrc::squares(self.active, sq));
         let promotion_rank = self.active.pawn_promoting_from_rank();
         let pawn_locs = self.locs(&[Piece::pawn(self.active)]);
         (
@@ -150,17 +219,58 @@ impl Board {
         )
     }
 
-    fn compute_castle_moves(&self, constraints: &MoveConstraints) -> Vec<Move> {
+    fn compute_castle_moves<E: Extend<Move>>(
+        &self,
+        constraints: &MoveConstraints,
+        origins: BitBoard,
+        dests: BitBoard,
+        dest: &mut E,
+    ) {
         let king_constraint = constraints.get(self.king(self.active));
         let (whites, blacks) = self.sides();
-        let p1 = |z: CastleZone| king_constraint.subsumes(z.uncontrolled_requirement());
-        let p2 = |z: CastleZone| !(whites | blacks).intersects(z.unoccupied_requirement());
+        let castling = self.castling;
+        let p0 = |z: CastleZone| {
+            let (_, king_src, king_dest) = castling.king_data(z);
+            origins.contains(king_src) && dests.contains(king_dest)
+        };
+        let p1 = |z: CastleZone| king_constraint.subsumes(castling.uncontrolled_requirement(z));
+        let p2 = |z: CastleZone| !(whites | blacks).intersects(castling.unoccupied_requirement(z));
+        let p3 = |z: CastleZone| !self.castle_exposes_king(z);
         let source = self.hash();
-        self.rights
-            .0
-            .iter()
-            .filter(|&z| p1(z) && p2(z))
-            .map(|zone| Move::Castle { source, zone })
-            .collect()
+        dest.extend(
+            self.rights
+                .0
+                .iter()
+                .filter(|&z| p0(z) && p1(z) && p2(z) && p3(z))
+                .map(|zone| Move::Castle {
+                    source,
+                    zone,
+                    castling,
+                }),
+        )
+    }
+
+    /// In Chess960 the castling rook may be the only piece blocking an
+    /// attack along the back rank on the square the king moves to, in
+    /// which case moving it would leave the king in check.
+    fn castle_exposes_king(&self, zone: CastleZone) -> bool {
+        let (_, king_src, king_dest) = self.castling.king_data(zone);
+        let (_, rook_src, rook_dest) = self.castling.rook_data(zone);
+        let passive = self.active.reflect();
+        let (whites, blacks) = self.sides();
+        let (whites, blacks) = match self.active {
+            Side::White => (
+                (whites - king_src - rook_src) | king_dest | rook_dest,
+                blacks,
+            ),
+            Side::Black => (
+                whites,
+                (blacks - king_src - rook_src) | king_dest | rook_dest,
+            ),
+        };
+        let attackers = self.locs(&[Piece::rook(passive), Piece::queen(passive)]);
+        Piece::rook(self.active)
+            .control(king_dest, whites, blacks)
+            .intersects(attackers)
     }
 }
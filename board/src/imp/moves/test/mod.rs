@@ -8,6 +8,7 @@ use myopic_core::*;
 use crate::ChessBoard;
 use crate::imp::Board;
 use crate::MoveComputeType;
+use crate::MoveList;
 use crate::mv::Move;
 
 type MoveSet = BTreeSet<Move>;
@@ -83,6 +84,39 @@ fn execute_test_impl(mut board: Board, moves: ExpectedMoves) {
             format_difference(expected_moves, actual_moves)
         );
     }
+    check_captures_quiets_partition(&mut board);
+    check_square_queries(&mut board);
+}
+
+fn check_captures_quiets_partition(board: &mut Board) {
+    let all: MoveSet = board.compute_moves(MoveComputeType::All).into_iter().collect();
+    let mut list = MoveList::default();
+    board.compute_moves_into(MoveComputeType::All, &mut list);
+    assert_eq!(all, list.iter().cloned().collect());
+    board.compute_moves_into(MoveComputeType::Captures, &mut list);
+    let captures: MoveSet = list.iter().cloned().collect();
+    board.compute_moves_into(MoveComputeType::Quiets, &mut list);
+    let quiets: MoveSet = list.iter().cloned().collect();
+    assert!(captures.is_disjoint(&quiets));
+    assert_eq!(all, captures.union(&quiets).cloned().collect());
+    assert!(captures.iter().all(|m| match m {
+        Move::Standard { capture, .. } => capture.is_some(),
+        Move::Castle { .. } => false,
+        _ => true,
+    }));
+}
+
+fn check_square_queries(board: &mut Board) {
+    let all: MoveSet = board.compute_moves(MoveComputeType::All).into_iter().collect();
+    for square in Square::iter() {
+        let from: MoveSet = board.moves_from(square).into_iter().collect();
+        let to: MoveSet = board.moves_to(square).into_iter().collect();
+        assert!(from.is_subset(&all) && to.is_subset(&all));
+        for mv in all.iter() {
+            assert_eq!(mv.compact().origin() == square, from.contains(mv));
+            assert_eq!(mv.compact().dest() == square, to.contains(mv));
+        }
+    }
 }
 
 fn format_difference(expected: MoveSet, actual: MoveSet) -> String {
@@ -10,6 +10,8 @@ use crate::parse::patterns;
 pub struct Positions {
     boards: [BitBoard; 12],
     hash: u64,
+    pawn_hash: u64,
+    material_key: u64,
     whites: BitBoard,
     blacks: BitBoard,
 }
@@ -36,6 +38,8 @@ impl FromStr for Positions {
             Ok(Positions {
                 boards: bitboards,
                 hash: hash_boards(&bitboards),
+                pawn_hash: hash_pawns(&bitboards),
+                material_key: compute_material_key(&bitboards),
                 whites: compute_whites(&bitboards),
                 blacks: compute_blacks(&bitboards),
             })
@@ -52,6 +56,8 @@ impl Reflectable for Positions {
         Positions {
             boards: new_boards,
             hash: hash_boards(&new_boards),
+            pawn_hash: hash_pawns(&new_boards),
+            material_key: compute_material_key(&new_boards),
             whites: compute_whites(&new_boards),
             blacks: compute_blacks(&new_boards),
         }
@@ -67,6 +73,33 @@ fn hash_boards(boards: &[BitBoard]) -> u64 {
         .fold(0u64, |a, b| a ^ b)
 }
 
+fn hash_pawns(boards: &[BitBoard]) -> u64 {
+    [Piece::WP, Piece::BP]
+        .iter()
+        .flat_map(|&p| {
+            boards[p as usize]
+                .into_iter()
+                .map(move |sq| hash::piece(p, sq))
+        })
+        .fold(0u64, |a, b| a ^ b)
+}
+
+/// The number of bits used for the count of each piece in the material
+/// key, no piece can have more than ten instances in a legal position.
+const MATERIAL_KEY_BITS: usize = 4;
+
+fn material_key_unit(piece: Piece) -> u64 {
+    1u64 << (MATERIAL_KEY_BITS * piece as usize)
+}
+
+fn compute_material_key(boards: &[BitBoard]) -> u64 {
+    boards
+        .iter()
+        .zip(Piece::all())
+        .map(|(&b, p)| b.size() as u64 * material_key_unit(p))
+        .sum()
+}
+
 fn compute_whites(boards: &[BitBoard]) -> BitBoard {
     boards.iter().take(6).fold(BitBoard::EMPTY, |a, &b| a | b)
 }
@@ -112,6 +145,8 @@ impl Positions {
         Positions {
             boards: dest,
             hash: initial_hash,
+            pawn_hash: hash_pawns(initial_boards),
+            material_key: compute_material_key(initial_boards),
             whites: compute_whites(&dest),
             blacks: compute_blacks(&dest),
         }
@@ -152,9 +187,18 @@ impl Positions {
         let mut locationset = BitBoard::EMPTY;
         for &location in locations.iter() {
             locationset ^= location;
-            self.hash ^= hash::piece(piece, location);
+            let feature = hash::piece(piece, location);
+            self.hash ^= feature;
+            if piece.is_pawn() {
+                self.pawn_hash ^= feature;
+            }
+            if self.boards[piece as usize].contains(location) {
+                self.material_key -= material_key_unit(piece);
+            } else {
+                self.material_key += material_key_unit(piece);
+            }
+            self.boards[piece as usize] ^= location;
         }
-        self.boards[piece as usize] ^= locationset;
         match piece.side() {
             Side::White => self.whites ^= locationset,
             Side::Black => self.blacks ^= locationset,
@@ -164,6 +208,14 @@ impl Positions {
     pub fn hash(&self) -> u64 {
         self.hash
     }
+
+    pub fn pawn_hash(&self) -> u64 {
+        self.pawn_hash
+    }
+
+    pub fn material_key(&self) -> u64 {
+        self.material_key
+    }
 }
 
 #[cfg(test)]
@@ -192,6 +244,8 @@ mod test {
         Positions {
             boards,
             hash: p_hash ^ n_hash,
+            pawn_hash: p_hash,
+            material_key: compute_material_key(&boards),
             whites: compute_whites(&boards),
             blacks: compute_blacks(&boards),
         }
@@ -1,10 +1,9 @@
-use std::str::FromStr;
-
-use anyhow::{anyhow, Error, Result};
+use anyhow::{anyhow, Result};
 
 use myopic_core::*;
 
 use crate::enumset::EnumSet;
+use crate::imp::positions::Positions;
 
 #[derive(Debug, Copy, Clone, PartialOrd, PartialEq, Eq)]
 pub struct Rights(pub EnumSet<CastleZone>);
@@ -15,26 +14,101 @@ impl Reflectable for Rights {
     }
 }
 
-impl FromStr for Rights {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if !crate::parse::patterns::fen_rights().is_match(s) {
-            Err(anyhow!("{}", s))
+/// Parse the castling rights field of a FEN string along with the squares
+/// the castling kings and rooks start on. In standard chess the field is
+/// the usual combination of KQkq. In Chess960 the rights may be given in
+/// the X-FEN form where KQkq refer to the outermost rook on that side of
+/// the king, or in the Shredder-FEN form where the file of each castling
+/// rook is given instead. Using rook files always implies Chess960.
+pub fn parse(s: &str, pieces: &Positions, chess960: bool) -> Result<(Rights, Castling)> {
+    if !crate::parse::patterns::fen_rights().is_match(s) {
+        return Err(anyhow!("{}", s));
+    }
+    let mut rights = EnumSet::new();
+    let mut uses_files = false;
+    let (mut king, mut kingside_rook, mut queenside_rook) = (None, None, None);
+    for c in s.chars().filter(|&c| c != '-') {
+        let side = if c.is_ascii_uppercase() {
+            Side::White
         } else {
-            let rights = CastleZone::iter()
-                .zip(vec!["K", "Q", "k", "q"].into_iter())
-                .filter(|(_, pat)| s.contains(pat))
-                .map(|(z, _)| z)
-                .collect();
-            Ok(Rights(rights))
+            Side::Black
+        };
+        let back_rank = match side {
+            Side::White => BitBoard::RANKS[0],
+            Side::Black => BitBoard::RANKS[7],
+        };
+        // All squares are considered on the first rank so both sides agree
+        let to_first_rank = |sq: Square| match side {
+            Side::White => sq,
+            Side::Black => sq.reflect(),
+        };
+        let king_src = (pieces.locs(Piece::king(side)) & back_rank)
+            .first()
+            .map_or(Square::E1, to_first_rank);
+        let rooks = (pieces.locs(Piece::rook(side)) & back_rank)
+            .iter()
+            .map(to_first_rank)
+            .collect::<Vec<_>>();
+        let (kingside, rook_src, king_src) = match c.to_ascii_uppercase() {
+            'K' if chess960 => (
+                true,
+                rooks.iter().cloned().filter(|&r| r < king_src).min(),
+                Some(king_src),
+            ),
+            'Q' if chess960 => (
+                false,
+                rooks.iter().cloned().filter(|&r| r > king_src).max(),
+                Some(king_src),
+            ),
+            'K' => (true, None, None),
+            'Q' => (false, None, None),
+            file => {
+                uses_files = true;
+                let rook = Square::from_index(7 - (file as usize - 'A' as usize));
+                (rook < king_src, Some(rook), Some(king_src))
+            }
+        };
+        let (zone, rook_entry) = if kingside {
+            (CastleZone::kingside(side), &mut kingside_rook)
+        } else {
+            (CastleZone::queenside(side), &mut queenside_rook)
+        };
+        if let Some(rook) = rook_src {
+            record(rook_entry, rook, s)?;
+        }
+        if let Some(square) = king_src {
+            record(&mut king, square, s)?;
+        }
+        rights.insert(zone);
+    }
+    let castling = if chess960 || uses_files {
+        Castling::chess960(
+            king.unwrap_or(Square::E1),
+            kingside_rook.unwrap_or(Square::H1),
+            queenside_rook.unwrap_or(Square::A1),
+        )?
+    } else {
+        Castling::STANDARD
+    };
+    Ok((Rights(rights), castling))
+}
+
+/// The castling squares of both sides must agree.
+fn record(entry: &mut Option<Square>, square: Square, rights: &str) -> Result<()> {
+    match *entry {
+        Some(existing) if existing != square => {
+            Err(anyhow!("Inconsistent castling squares in {}", rights))
+        }
+        _ => {
+            *entry = Some(square);
+            Ok(())
         }
     }
 }
 
-fn compute_rights_removed(srcdest: BitBoard) -> EnumSet<CastleZone> {
+fn compute_rights_removed(srcdest: BitBoard, castling: Castling) -> EnumSet<CastleZone> {
     CastleZone::iter()
-        .filter(|x| srcdest.intersects(x.source_squares()))
+        .filter(|&x| srcdest.intersects(castling.source_squares(x)))
         .collect()
 }
 
@@ -49,7 +123,7 @@ impl Rights {
         )
     }
 
-    pub fn remove_rights(self, srcdest: BitBoard) -> Rights {
-        Rights(self.0 - compute_rights_removed(srcdest))
+    pub fn remove_rights(self, srcdest: BitBoard, castling: Castling) -> Rights {
+        Rights(self.0 - compute_rights_removed(srcdest, castling))
     }
 }
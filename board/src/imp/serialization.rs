@@ -0,0 +1,96 @@
+use serde::de::Error as DeError;
+use serde::ser::Error as SerError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::GameRecord;
+use crate::imp::Board;
+
+/// A board is serialized as the record of the game played on it so that
+/// the history is restored when it is deserialized.
+impl Serialize for Board {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        GameRecord::of(self)
+            .map_err(S::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Board {
+    fn deserialize<D>(deserializer: D) -> Result<Board, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        GameRecord::deserialize(deserializer)?
+            .restore()
+            .map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+
+    use myopic_core::*;
+
+    use crate::{ChessBoard, Move};
+
+    use super::*;
+
+    #[test]
+    fn core_types_round_trip() -> Result<()> {
+        let json = serde_json::to_string(&(Square::E4, Piece::BQ, Side::Black))?;
+        assert_eq!(
+            (Square::E4, Piece::BQ, Side::Black),
+            serde_json::from_str(&json)?
+        );
+        let json = serde_json::to_string(&(CastleZone::WQ, Square::A1 | Square::H8))?;
+        assert_eq!(
+            (CastleZone::WQ, Square::A1 | Square::H8),
+            serde_json::from_str::<(CastleZone, BitBoard)>(&json)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn move_round_trip() -> Result<()> {
+        let mut board = "r3k2r/1P6/8/3pP3/8/8/8/R3K2R w KQkq d6 0 2".parse::<Board>()?;
+        for uci in &["e1g1", "e5d6", "b7a8q", "b7b8n", "a1a8"] {
+            let mv = board.parse_uci(uci)?;
+            let json = serde_json::to_string(&mv)?;
+            assert_eq!(mv, serde_json::from_str::<Move>(&json)?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn board_round_trip() -> Result<()> {
+        let mut board = crate::start();
+        board.play_uci("e2e4 g8f6 e4e5 d7d5 e5d6")?;
+        board.make_null()?;
+        board.play_uci("f1b5")?;
+        let json = serde_json::to_string(&board)?;
+        assert_eq!(
+            r#"{"start_fen":"rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1","moves":["e2e4","g8f6","e4e5","d7d5","e5d6","0000","f1b5"]}"#,
+            json
+        );
+        let mut parsed = serde_json::from_str::<Board>(&json)?;
+        assert_eq!(board.to_fen(), parsed.to_fen());
+        assert_eq!(board.position_count(), parsed.position_count());
+        parsed.unmake()?;
+        parsed.unmake_null()?;
+        assert_eq!(
+            "rnbqkb1r/ppp1pppp/3P1n2/8/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 3",
+            parsed.to_fen()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn board_with_illegal_move_is_error() {
+        let json = r#"{"start_fen":"rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1","moves":["e2e5"]}"#;
+        assert!(serde_json::from_str::<Board>(json).is_err());
+    }
+}
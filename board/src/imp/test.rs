@@ -32,6 +32,7 @@ impl From<TestBoard> for Board {
             history: History::new(test_board.history_count),
             pieces,
             rights: Rights(test_board.castle_rights),
+            castling: Castling::STANDARD,
             active: test_board.active,
             enpassant: test_board.enpassant,
             clock: test_board.clock,
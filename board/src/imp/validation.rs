@@ -0,0 +1,259 @@
+use std::fmt::{Display, Formatter};
+
+use myopic_core::*;
+
+use crate::ChessBoard;
+use crate::enumset::EnumSet;
+use crate::imp::Board;
+use crate::imp::rights::Rights;
+
+/// The reasons a FEN string can be rejected when constructing a board
+/// with validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FenError {
+    /// The string does not have the structure of a FEN string
+    Malformed(String),
+    /// The given side does not have exactly one king
+    KingCount(Side, usize),
+    /// The given side has more than eight pawns
+    TooManyPawns(Side, usize),
+    /// The given side has more than sixteen pieces
+    TooManyPieces(Side, usize),
+    /// There is a pawn on the first or last rank
+    PawnOnBackRank(Square),
+    /// The king or rook required for the castling right is missing
+    InvalidCastlingRight(CastleZone),
+    /// The enpassant square cannot follow a double pawn push
+    InvalidEnpassant(Square),
+    /// The side which is not to move is in check
+    InactiveSideInCheck,
+}
+
+impl Display for FenError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FenError::Malformed(fen) => write!(f, "Cannot parse FEN {}", fen),
+            FenError::KingCount(side, n) => write!(f, "Side {} has {} kings", side, n),
+            FenError::TooManyPawns(side, n) => write!(f, "Side {} has {} pawns", side, n),
+            FenError::TooManyPieces(side, n) => write!(f, "Side {} has {} pieces", side, n),
+            FenError::PawnOnBackRank(sq) => write!(f, "Pawn on back rank square {}", sq),
+            FenError::InvalidCastlingRight(zone) => {
+                write!(f, "Castling right {} without king and rook", zone)
+            }
+            FenError::InvalidEnpassant(sq) => write!(f, "Impossible enpassant square {}", sq),
+            FenError::InactiveSideInCheck => write!(f, "The side not to move is in check"),
+        }
+    }
+}
+
+impl std::error::Error for FenError {}
+
+impl Board {
+    /// Parse a board from a FEN string checking that the position could
+    /// occur in a legal game, the first rule found to be violated is
+    /// returned as the error.
+    pub fn from_fen_strict(fen: &str) -> Result<Board, FenError> {
+        Board::from_fen_validated(fen, false)
+    }
+
+    /// Parse a board from a FEN string like [Board::from_fen_strict]
+    /// except that castling rights without the required king and rook
+    /// and an impossible enpassant square are removed rather than being
+    /// treated as errors.
+    pub fn from_fen_lenient(fen: &str) -> Result<Board, FenError> {
+        Board::from_fen_validated(fen, true)
+    }
+
+    fn from_fen_validated(fen: &str, repair: bool) -> Result<Board, FenError> {
+        let mut board = fen
+            .parse::<Board>()
+            .map_err(|_| FenError::Malformed(fen.to_owned()))?;
+        board.validate_material()?;
+        let invalid_rights = board.invalid_rights();
+        if let Some(zone) = invalid_rights.iter().next() {
+            if repair {
+                board.rights = Rights(board.rights.0 - invalid_rights);
+            } else {
+                return Err(FenError::InvalidCastlingRight(zone));
+            }
+        }
+        if let Some(square) = board.enpassant.filter(|&sq| !board.is_valid_enpassant(sq)) {
+            if repair {
+                board.enpassant = None;
+            } else {
+                return Err(FenError::InvalidEnpassant(square));
+            }
+        }
+        if board.passive_in_check() {
+            return Err(FenError::InactiveSideInCheck);
+        }
+        board.clear_cache();
+        Ok(board)
+    }
+
+    fn validate_material(&self) -> Result<(), FenError> {
+        for &side in &[Side::White, Side::Black] {
+            let kings = self.locs(&[Piece::king(side)]).size();
+            if kings != 1 {
+                return Err(FenError::KingCount(side, kings));
+            }
+            let pawns = self.locs(&[Piece::pawn(side)]).size();
+            if pawns > 8 {
+                return Err(FenError::TooManyPawns(side, pawns));
+            }
+            let pieces = self.side(side).size();
+            if pieces > 16 {
+                return Err(FenError::TooManyPieces(side, pieces));
+            }
+        }
+        let back_ranks = BitBoard::RANKS[0] | BitBoard::RANKS[7];
+        match (self.locs(&[Piece::WP, Piece::BP]) & back_ranks)
+            .into_iter()
+            .next()
+        {
+            Some(square) => Err(FenError::PawnOnBackRank(square)),
+            None => Ok(()),
+        }
+    }
+
+    fn invalid_rights(&self) -> EnumSet<CastleZone> {
+        self.rights
+            .0
+            .iter()
+            .filter(|&zone| {
+                let (king, king_src, _) = self.castling.king_data(zone);
+                let (rook, rook_src, _) = self.castling.rook_data(zone);
+                self.piece(king_src) != Some(king) || self.piece(rook_src) != Some(rook)
+            })
+            .collect()
+    }
+
+    /// The enpassant square must be directly behind a passive pawn which
+    /// has just moved two squares forward from its starting square.
+    fn is_valid_enpassant(&self, square: Square) -> bool {
+        let passive = self.active.reflect();
+        let pawn = square.next(passive.pawn_dir());
+        let origin = square.next(passive.pawn_dir().reflect());
+        match (pawn, origin) {
+            (Some(pawn), Some(origin)) => {
+                passive.pawn_third_rank().contains(pawn)
+                    && self.piece(pawn) == Some(Piece::pawn(passive))
+                    && self.piece(square).is_none()
+                    && self.piece(origin).is_none()
+            }
+            _ => false,
+        }
+    }
+
+    fn passive_in_check(&self) -> bool {
+        let (whites, blacks) = self.sides();
+        let passive_king = self.king(self.active.reflect());
+        Piece::of(self.active).any(|p| {
+            self.locs(&[p])
+                .iter()
+                .any(|sq| p.control(sq, whites, blacks).contains(passive_king))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_strict_error(expected: FenError, fen: &str) {
+        assert_eq!(
+            Err(expected),
+            Board::from_fen_strict(fen).map(|b| b.to_fen())
+        );
+    }
+
+    #[test]
+    fn valid_positions() {
+        for fen in &[
+            crate::STARTPOS_FEN,
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "8/8/8/8/3Pp3/8/8/k6K b - d3 0 1",
+        ] {
+            assert_eq!(
+                Ok(fen.to_string()),
+                Board::from_fen_strict(fen).map(|b| b.to_fen())
+            );
+        }
+    }
+
+    #[test]
+    fn malformed() {
+        assert_strict_error(FenError::Malformed("not a fen".to_owned()), "not a fen");
+    }
+
+    #[test]
+    fn king_count() {
+        assert_strict_error(
+            FenError::KingCount(Side::White, 2),
+            "4k3/8/8/8/8/8/8/3KK3 w - - 0 1",
+        );
+        assert_strict_error(
+            FenError::KingCount(Side::Black, 0),
+            "8/8/8/8/8/8/8/4K3 w - - 0 1",
+        );
+    }
+
+    #[test]
+    fn too_much_material() {
+        assert_strict_error(
+            FenError::TooManyPawns(Side::White, 9),
+            "4k3/8/8/8/7P/8/PPPPPPPP/4K3 w - - 0 1",
+        );
+        assert_strict_error(
+            FenError::TooManyPieces(Side::Black, 17),
+            "nnnnknnn/pppppppp/n7/8/8/8/8/4K3 w - - 0 1",
+        );
+    }
+
+    #[test]
+    fn pawn_on_back_rank() {
+        assert_strict_error(
+            FenError::PawnOnBackRank(Square::A1),
+            "4k3/8/8/8/8/8/8/P3K3 w - - 0 1",
+        );
+    }
+
+    #[test]
+    fn castling_rights() {
+        let fen = "r3k3/8/8/8/8/8/8/4K2R w KQkq - 0 1";
+        assert_strict_error(FenError::InvalidCastlingRight(CastleZone::WQ), fen);
+        assert_eq!(
+            Ok("r3k3/8/8/8/8/8/8/4K2R w Kq - 0 1".to_owned()),
+            Board::from_fen_lenient(fen).map(|b| b.to_fen())
+        );
+    }
+
+    #[test]
+    fn enpassant() {
+        let fen = "4k3/8/8/3pP3/8/8/8/4K3 w - e6 0 2";
+        assert_strict_error(FenError::InvalidEnpassant(Square::E6), fen);
+        assert_strict_error(
+            FenError::InvalidEnpassant(Square::D3),
+            "4k3/8/8/3pP3/8/8/8/4K3 w - d3 0 2",
+        );
+        assert_eq!(
+            Ok("4k3/8/8/3pP3/8/8/8/4K3 w - - 0 2".to_owned()),
+            Board::from_fen_lenient(fen).map(|b| b.to_fen())
+        );
+    }
+
+    #[test]
+    fn inactive_side_in_check() {
+        let fen = "4k3/8/8/8/8/8/8/4K2R w - - 0 1";
+        assert!(Board::from_fen_strict(fen).is_ok());
+        assert_strict_error(
+            FenError::InactiveSideInCheck,
+            "4k2R/8/8/8/8/8/8/4K3 w - - 0 1",
+        );
+        assert_eq!(
+            Err(FenError::InactiveSideInCheck),
+            Board::from_fen_lenient("4k2R/8/8/8/8/8/8/4K3 w - - 0 1").map(|b| b.to_fen())
+        );
+    }
+}
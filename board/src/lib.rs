@@ -1,18 +1,27 @@
 #[macro_use]
 extern crate lazy_static;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 
+pub use compact::CompactMove;
+pub use movelist::{MoveList, MAX_MOVES};
 pub use mv::Move;
 pub use myopic_core::*;
 pub use parse::uci::UciMove;
+pub use record::{GameHistory, GameRecord, HistoryIter};
 
 use crate::enumset::EnumSet;
-pub use crate::imp::Board;
+pub use crate::imp::{Board, FenError};
 
+mod compact;
 mod imp;
+mod movelist;
 mod mv;
 mod parse;
+mod record;
+pub mod epd;
+pub mod perft;
+pub mod pgn;
 
 /// The start position of a chess game encoded in FEN format
 pub const STARTPOS_FEN: &'static str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
@@ -29,6 +38,12 @@ pub enum MoveComputeType {
     /// If a promoting move causes check then all promoting moves for
     /// the four different target pieces will be included for that pawn.
     AttacksChecks,
+    /// The legal captures, including enpassant, along with all legal
+    /// promotions whether they capture or not.
+    Captures,
+    /// The legal moves which are not included by the Captures type so
+    /// that together the two types partition all the legal moves.
+    Quiets,
 }
 
 /// Represents the possible ways a game can be terminated, we only
@@ -39,10 +54,39 @@ pub enum MoveComputeType {
 /// drawn.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
 pub enum Termination {
-    Draw,
+    Draw(DrawReason),
     Loss,
 }
 
+/// The reasons a game can be drawn. Under FIDE rules a threefold
+/// repetition or fifty moves without a capture or pawn move only allow
+/// a player to claim a draw whereas the other reasons end the game
+/// immediately. A checkmate always takes precedence over the move
+/// count rules.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
+pub enum DrawReason {
+    /// The active side has no legal moves and is not in check
+    Stalemate,
+    /// Neither side has the material required to deliver checkmate
+    InsufficientMaterial,
+    /// The current position has occurred at least three times
+    ThreefoldRepetition,
+    /// The last 100 half moves contain no capture or pawn move
+    FiftyMoveRule,
+    /// The current position has occurred at least five times
+    FivefoldRepetition,
+    /// The last 150 half moves contain no capture or pawn move
+    SeventyFiveMoveRule,
+}
+
+impl DrawReason {
+    /// Return true if the draw must be claimed by a player rather than
+    /// ending the game automatically.
+    pub fn is_claimable(&self) -> bool {
+        matches!(self, DrawReason::ThreefoldRepetition | DrawReason::FiftyMoveRule)
+    }
+}
+
 /// Represents the individual components which make up a board position
 /// encoded as a FEN string.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
@@ -67,15 +111,61 @@ pub trait ChessBoard: Clone + Send {
     /// creating moves) then the results are undefined.
     fn make(&mut self, action: Move) -> Result<()>;
 
+    /// Evolves the position by making the given move only if it is legal
+    /// in this position, otherwise an error is returned and the position
+    /// is left unchanged. This should be preferred over make for moves
+    /// from untrusted sources like user input or hash tables.
+    fn make_checked(&mut self, action: Move) -> Result<()> {
+        if self.is_legal(&action) {
+            self.make(action)
+        } else {
+            Err(anyhow!("{} is not legal in {}", action, self.to_fen()))
+        }
+    }
+
     /// Reverses and returns the move which was made last. If no move has
     /// been made yet then an error is returned.
     fn unmake(&mut self) -> Result<Move>;
 
+    /// Passes the turn to the other side without moving any piece and
+    /// removes any enpassant square. This is not a legal chess move, it
+    /// exists for search techniques like null move pruning. If the active
+    /// side is in check then an error will be returned.
+    fn make_null(&mut self) -> Result<()>;
+
+    /// Reverses the null move which was made last. If no move has been
+    /// made yet or the last move was not a null move then an error is
+    /// returned.
+    fn unmake_null(&mut self) -> Result<()>;
+
     /// Compute a vector of all the legal moves in this position for the
     /// given computation type. Note there is no particular ordering to the
     /// move vector.
     fn compute_moves(&mut self, computation_type: MoveComputeType) -> Vec<Move>;
 
+    /// Clear the given move list and fill it with the moves which would be
+    /// returned by compute_moves for the same computation type. Reusing a
+    /// list avoids allocating during move generation.
+    fn compute_moves_into(&mut self, computation_type: MoveComputeType, dest: &mut MoveList);
+
+    /// Compute the legal moves of the piece on the given square, this is
+    /// empty if the square does not hold a piece of the active side.
+    fn moves_from(&mut self, square: Square) -> Vec<Move>;
+
+    /// Compute the legal moves which finish on the given square, castling
+    /// moves are included if the king finishes on the square.
+    fn moves_to(&mut self, square: Square) -> Vec<Move>;
+
+    /// Check whether the given move was created for this position and
+    /// agrees with the pieces on the board and the way they move. This
+    /// does not check whether the move leaves the king of the moving
+    /// side in check.
+    fn is_pseudo_legal(&self, action: &Move) -> bool;
+
+    /// Check whether the given move is one of the legal moves in this
+    /// position without computing all of the legal moves.
+    fn is_legal(&mut self, action: &Move) -> bool;
+
     /// Compute the termination state of this node. If it is not terminal
     /// nothing is returned, if it is then the manner of termination is
     /// returned wrapped inside an Option. The termination can be only a
@@ -87,6 +177,27 @@ pub trait ChessBoard: Clone + Send {
     /// Determines whether the active side is in a state of check.
     fn in_check(&mut self) -> bool;
 
+    /// Determines whether making the given legal move would put the
+    /// passive side in check without making the move.
+    fn gives_check(&mut self, action: &Move) -> bool;
+
+    /// Return the locations of the pieces on the given side which attack
+    /// the given square, pieces of either side block sliding attacks.
+    fn attackers_to(&mut self, square: Square, side: Side) -> BitBoard;
+
+    /// Return the locations of the passive pieces which are giving check
+    /// to the active king.
+    fn checkers(&mut self) -> BitBoard;
+
+    /// Return the locations of the pieces on the given side which are
+    /// pinned to their own king by an enemy slider.
+    fn pinned_pieces(&mut self, side: Side) -> BitBoard;
+
+    /// Return the locations of the active pieces which are the only piece
+    /// between an active slider and the passive king, moving one of these
+    /// off the ray between them gives a discovered check.
+    fn discovered_check_candidates(&mut self) -> BitBoard;
+
     /// Return the locations of all pieces on the given side.
     fn side(&self, side: Side) -> BitBoard;
 
@@ -96,6 +207,21 @@ pub trait ChessBoard: Clone + Send {
     /// Returns the Zobrist hash of this position.
     fn hash(&self) -> u64;
 
+    /// Returns the Zobrist hash of the position which would follow the
+    /// given legal move without making it, this is always equal to the
+    /// hash of this position after the move is made.
+    fn hash_after(&self, action: &Move) -> u64;
+
+    /// Return a hash of the locations of the pawns of both sides only so
+    /// that positions with the same pawn structure share a value.
+    fn pawn_hash(&self) -> u64;
+
+    /// Return a signature of the material on the board which is equal for
+    /// two positions exactly when they contain the same number of each
+    /// piece. The count of each piece occupies four bits with the pieces
+    /// in declaration order starting from the lowest bits.
+    fn material_key(&self) -> u64;
+
     /// Return the active side in this position, i.e. the one whose turn it is.
     fn active(&self) -> Side;
 
@@ -111,6 +237,25 @@ pub trait ChessBoard: Clone + Send {
     /// Return the piece occupying the given location.
     fn piece(&self, location: Square) -> Option<Piece>;
 
+    /// Return every move made on this board in the order they were made,
+    /// null moves are given as None.
+    fn move_history(&self) -> Vec<Option<Move>>;
+
+    /// Return the position before any moves were made on this board
+    /// along with the moves made since, an error is returned if the
+    /// moves cannot be unmade.
+    fn history(&self) -> Result<GameHistory<Self>> {
+        let moves = self.move_history();
+        let mut start = self.clone();
+        for mv in moves.iter().rev() {
+            match mv {
+                Some(_) => start.unmake().map(|_| ())?,
+                None => start.unmake_null()?,
+            }
+        }
+        Ok(GameHistory { start, moves })
+    }
+
     /// Return the half move clock value at this position.
     fn half_move_clock(&self) -> usize;
 
@@ -120,6 +265,10 @@ pub trait ChessBoard: Clone + Send {
     /// Return the remaining castling rights from this position.
     fn remaining_rights(&self) -> EnumSet<CastleZone>;
 
+    /// Return the squares the kings and rooks started on in this game
+    /// which determine how castling moves are made.
+    fn castling(&self) -> Castling;
+
     /// Parse the given string as a sequence of pgn encoded moves
     /// starting from the current position. The moves are then
     /// made one by one. The sequence of moves which were made
@@ -154,6 +303,14 @@ pub trait ChessBoard: Clone + Send {
         ])
     }
 
+    /// Draw a diagram of this position with the white pieces at the
+    /// bottom, the side to move is marked beside its back rank.
+    fn render(&self, options: &RenderOptions) -> String {
+        render::render_grid(options, Some(self.active()), |sq| {
+            self.piece(sq).map(|p| p.symbol(options.unicode))
+        })
+    }
+
     /// Returns the locations of all pieces on the board.
     fn all_pieces(&self) -> BitBoard {
         let (w, b) = self.sides();
@@ -163,8 +320,11 @@ pub trait ChessBoard: Clone + Send {
 
 #[cfg(test)]
 mod uci_conversion_test {
+    use anyhow::Result;
+
     use myopic_core::*;
 
+    use crate::{Board, ChessBoard};
     use crate::mv::Move;
 
     #[test]
@@ -203,7 +363,8 @@ mod uci_conversion_test {
             "e1g1",
             Move::Castle {
                 source: 1u64,
-                zone: CastleZone::WK
+                zone: CastleZone::WK,
+                castling: Castling::STANDARD,
             }
             .uci_format()
         );
@@ -211,7 +372,8 @@ mod uci_conversion_test {
             "e1c1",
             Move::Castle {
                 source: 1u64,
-                zone: CastleZone::WQ
+                zone: CastleZone::WQ,
+                castling: Castling::STANDARD,
             }
             .uci_format()
         );
@@ -219,7 +381,8 @@ mod uci_conversion_test {
             "e8g8",
             Move::Castle {
                 source: 8u64,
-                zone: CastleZone::BK
+                zone: CastleZone::BK,
+                castling: Castling::STANDARD,
             }
             .uci_format()
         );
@@ -227,12 +390,44 @@ mod uci_conversion_test {
             "e8c8",
             Move::Castle {
                 source: 8u64,
-                zone: CastleZone::BQ
+                zone: CastleZone::BQ,
+                castling: Castling::STANDARD,
             }
             .uci_format()
         );
     }
 
+    #[test]
+    fn test_chess960_castling_conversion() -> Result<()> {
+        let castling = Castling::chess960(Square::F1, Square::G1, Square::B1)?;
+        assert_eq!(
+            "f1g1",
+            Move::Castle {
+                source: 1u64,
+                zone: CastleZone::WK,
+                castling,
+            }
+            .uci_format()
+        );
+        assert_eq!(
+            "f8b8",
+            Move::Castle {
+                source: 8u64,
+                zone: CastleZone::BQ,
+                castling,
+            }
+            .uci_format()
+        );
+        let mut board =
+            Board::from_fen_chess960("1r3kr1/1p4p1/8/8/8/8/1P3PP1/1R3KR1 w KQkq - 0 1")?;
+        board.play_uci("f1g1 f8b8")?;
+        assert_eq!(
+            "2kr2r1/1p4p1/8/8/8/8/1P3PP1/1R3RK1 w - - 2 2",
+            board.to_fen()
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_promotion_conversion() {
         assert_eq!(
@@ -263,3 +458,45 @@ mod uci_conversion_test {
         )
     }
 }
+
+#[cfg(test)]
+mod render_test {
+    use anyhow::Result;
+
+    use myopic_core::*;
+
+    use crate::ChessBoard;
+
+    #[test]
+    fn render_after_move() -> Result<()> {
+        let mut board = crate::start();
+        board.play_uci("e2e4")?;
+        let options = RenderOptions {
+            highlights: Square::E2 | Square::E4,
+            ..RenderOptions::default()
+        };
+        let expected = vec![
+            "8  r  n  b  q  k  b  n  r  <",
+            "7  p  p  p  p  p  p  p  p",
+            "6  .  .  .  .  .  .  .  .",
+            "5  .  .  .  .  .  .  .  .",
+            "4  .  .  .  . [P] .  .  .",
+            "3  .  .  .  .  .  .  .  .",
+            "2  P  P  P  P [.] P  P  P",
+            "1  R  N  B  Q  K  B  N  R",
+            "   a  b  c  d  e  f  g  h",
+        ];
+        assert_eq!(expected.join("\n"), board.render(&options));
+        let unicode = RenderOptions {
+            unicode: true,
+            labels: false,
+            active_marker: false,
+            ..options
+        };
+        assert_eq!(
+            " ♜  ♞  ♝  ♛  ♚  ♝  ♞  ♜",
+            board.render(&unicode).lines().next().unwrap()
+        );
+        Ok(())
+    }
+}
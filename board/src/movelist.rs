@@ -0,0 +1,169 @@
+use std::fmt::{Debug, Formatter};
+use std::ops::{Deref, DerefMut};
+
+use crate::{CastleZone, Castling, Move};
+
+/// The maximum number of moves a move list can hold, no legal position
+/// has more than 218 legal moves.
+pub const MAX_MOVES: usize = 256;
+
+/// Placeholder occupying the unused slots of a move list.
+const EMPTY_SLOT: Move = Move::Castle {
+    source: 0,
+    zone: CastleZone::WK,
+    castling: Castling::STANDARD,
+};
+
+/// A fixed capacity list of moves stored inline so that it can live on
+/// the stack. Move generation can fill a caller provided list which is
+/// cleared and reused between positions to avoid allocating a new vector
+/// at every node of a search. The list dereferences to a slice of the
+/// moves it currently holds.
+#[derive(Clone)]
+pub struct MoveList {
+    moves: [Move; MAX_MOVES],
+    len: usize,
+}
+
+impl Default for MoveList {
+    fn default() -> Self {
+        MoveList {
+            moves: [EMPTY_SLOT; MAX_MOVES],
+            len: 0,
+        }
+    }
+}
+
+impl MoveList {
+    /// Append a move to the end of this list, panics if the list is full
+    /// which cannot happen when filled with the moves of a legal position.
+    pub fn push(&mut self, mv: Move) {
+        if self.len == MAX_MOVES {
+            panic!("Move list capacity of {} exceeded", MAX_MOVES);
+        }
+        self.moves[self.len] = mv;
+        self.len += 1;
+    }
+
+    /// Remove and return the last move in this list.
+    pub fn pop(&mut self) -> Option<Move> {
+        if self.len == 0 {
+            None
+        } else {
+            self.len -= 1;
+            Some(std::mem::replace(&mut self.moves[self.len], EMPTY_SLOT))
+        }
+    }
+
+    /// Remove the move at the given index and return it, the last move
+    /// in the list takes its place so this does not preserve ordering.
+    pub fn swap_remove(&mut self, index: usize) -> Move {
+        if index >= self.len {
+            panic!(
+                "Index {} out of bounds for move list of length {}",
+                index, self.len
+            );
+        }
+        self.moves.swap(index, self.len - 1);
+        self.pop().unwrap()
+    }
+
+    /// Retain only the moves which satisfy the given predicate, the order
+    /// of the retained moves is preserved.
+    pub fn retain<F: FnMut(&Move) -> bool>(&mut self, mut predicate: F) {
+        let mut retained = 0;
+        for i in 0..self.len {
+            if predicate(&self.moves[i]) {
+                self.moves.swap(retained, i);
+                retained += 1;
+            }
+        }
+        self.truncate(retained);
+    }
+
+    /// Shorten this list to the given length, has no effect if the list is
+    /// already shorter.
+    pub fn truncate(&mut self, len: usize) {
+        while self.len > len {
+            self.pop();
+        }
+    }
+
+    /// Remove all the moves from this list.
+    pub fn clear(&mut self) {
+        self.truncate(0)
+    }
+}
+
+impl Deref for MoveList {
+    type Target = [Move];
+
+    fn deref(&self) -> &Self::Target {
+        &self.moves[..self.len]
+    }
+}
+
+impl DerefMut for MoveList {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.moves[..self.len]
+    }
+}
+
+impl Extend<Move> for MoveList {
+    fn extend<T: IntoIterator<Item = Move>>(&mut self, iter: T) {
+        for mv in iter {
+            self.push(mv)
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a Move;
+    type IntoIter = std::slice::Iter<'a, Move>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl PartialEq for MoveList {
+    fn eq(&self, other: &Self) -> bool {
+        self.deref() == other.deref()
+    }
+}
+
+impl Eq for MoveList {}
+
+impl Debug for MoveList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ChessBoard, MoveComputeType};
+
+    use super::*;
+
+    #[test]
+    fn push_pop_and_remove() {
+        let mut board = crate::start();
+        let moves = board.compute_moves(MoveComputeType::All);
+        let mut list = MoveList::default();
+        assert!(list.is_empty());
+        list.extend(moves.iter().cloned());
+        assert_eq!(20, list.len());
+        assert_eq!(moves.as_slice(), &list[..]);
+        assert_eq!(moves[0], list.swap_remove(0));
+        assert_eq!(moves[19], list[0]);
+        assert_eq!(Some(moves[18].clone()), list.pop());
+        let is_g_file = |m: &Move| m.uci_format().starts_with('g');
+        let expected = list.iter().cloned().filter(is_g_file).collect::<Vec<_>>();
+        list.retain(is_g_file);
+        assert_eq!(4, list.len());
+        assert_eq!(expected.as_slice(), &list[..]);
+        list.clear();
+        assert_eq!(None, list.pop());
+    }
+}
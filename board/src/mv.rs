@@ -5,9 +5,10 @@ use anyhow::Result;
 
 use myopic_core::{Reflectable, Side};
 
-use crate::{CastleZone, Piece, Square};
+use crate::{CastleZone, Castling, ChessBoard, CompactMove, Piece, Square};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Move {
     Standard {
         source: u64,
@@ -33,6 +34,7 @@ pub enum Move {
     Castle {
         source: u64,
         zone: CastleZone,
+        castling: Castling,
     },
 }
 
@@ -80,13 +82,30 @@ impl Display for Move {
                 capture,
                 ..
             } => write!(f, "e{}{}{}{}", side, from, dest, capture),
-            Move::Castle { zone, .. } => write!(f, "c{}", zone),
+            &Move::Castle { zone, castling, .. } => {
+                write!(f, "c{}", zone)?;
+                if castling.is_chess960() {
+                    // The first rank squares of the king and both rooks
+                    write!(
+                        f,
+                        "{}{}{}",
+                        castling.king_data(CastleZone::WK).1,
+                        castling.rook_data(CastleZone::WK).1,
+                        castling.rook_data(CastleZone::WQ).1
+                    )?;
+                }
+                Ok(())
+            }
         }
     }
 }
 
 #[cfg(test)]
 impl Move {
+    /// Parse a move from the format written by its [Display] implementation.
+    /// Castles in Chess960 carry the first rank squares of the king and the
+    /// kingside and queenside rooks after the zone, without them standard
+    /// castling is assumed.
     pub fn from(s: &str, source: u64) -> Result<Move> {
         match s.chars().next() {
             None => Err(anyhow::anyhow!("Cannot parse move from empty string!")),
@@ -115,6 +134,15 @@ impl Move {
                 'c' => Ok(Move::Castle {
                     source,
                     zone: slice(s, 1, 2).parse()?,
+                    castling: if s.len() > 3 {
+                        Castling::chess960(
+                            slice(s, 3, 2).parse()?,
+                            slice(s, 5, 2).parse()?,
+                            slice(s, 7, 2).parse()?,
+                        )?
+                    } else {
+                        Castling::STANDARD
+                    },
                 }),
                 _ => Err(anyhow::anyhow!("Cannot parse {} as a move", s)),
             },
@@ -143,7 +171,7 @@ mod test {
 
     use myopic_core::Side;
 
-    use crate::{CastleZone, Piece, Square};
+    use crate::{Board, CastleZone, Castling, ChessBoard, MoveComputeType, Piece, Square};
     use crate::mv::Move;
 
     #[test]
@@ -216,12 +244,33 @@ mod test {
         assert_eq!(
             Move::Castle {
                 source: 0,
-                zone: CastleZone::BK
+                zone: CastleZone::BK,
+                castling: Castling::STANDARD,
             },
             Move::from("cbk", 0u64)?
         );
         Ok(())
     }
+
+    #[test]
+    fn chess960_castle_round_trip() -> Result<()> {
+        let mut board =
+            Board::from_fen_chess960("1r3kr1/1p4p1/8/8/8/8/1P3PP1/1R3KR1 w KQkq - 0 1")?;
+        let castles = board
+            .compute_moves(MoveComputeType::All)
+            .into_iter()
+            .filter(|m| matches!(m, Move::Castle { .. }))
+            .collect::<Vec<_>>();
+        assert_eq!(2, castles.len());
+        for castle in castles {
+            let encoded = castle.to_string();
+            assert_eq!(9, encoded.len(), "{}", encoded);
+            let parsed = Move::from(encoded.as_str(), castle.source())?;
+            assert_eq!(castle, parsed);
+            assert!(board.is_legal(&parsed));
+        }
+        Ok(())
+    }
 }
 
 impl Move {
@@ -234,14 +283,30 @@ impl Move {
         }
     }
 
-    /// Convert this move into a human readable uci long format string.
+    /// Pack this move into sixteen bits, see [CompactMove].
+    pub fn compact(&self) -> CompactMove {
+        CompactMove::from(self)
+    }
+
+    /// Convert this move into standard algebraic notation in the context
+    /// of the given position, see [crate::parse::pgn::to_san].
+    pub fn to_san<B: ChessBoard>(&self, board: &mut B) -> Result<String> {
+        crate::parse::pgn::to_san(board, self)
+    }
+
+    /// Convert this move into a human readable uci long format string,
+    /// castling in Chess960 is encoded as the king capturing its rook.
     pub fn uci_format(&self) -> String {
         match self {
             Move::Standard { from, dest, .. } => format!("{}{}", from, dest),
             Move::Enpassant { from, dest, .. } => format!("{}{}", from, dest),
-            Move::Castle { zone, .. } => {
-                let (_, src, dest) = zone.king_data();
-                format!("{}{}", src, dest)
+            &Move::Castle { zone, castling, .. } => {
+                let (_, src, dest) = castling.king_data(zone);
+                if castling.is_chess960() {
+                    format!("{}{}", src, castling.rook_data(zone).1)
+                } else {
+                    format!("{}{}", src, dest)
+                }
             }
             Move::Promotion {
                 from,
@@ -305,9 +370,10 @@ impl Move {
                 dest: dest.reflect(),
                 capture: capture.reflect(),
             },
-            &Move::Castle { zone, .. } => Move::Castle {
+            &Move::Castle { zone, castling, .. } => Move::Castle {
                 source: new_source,
                 zone: zone.reflect(),
+                castling,
             },
         }
     }
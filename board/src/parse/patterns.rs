@@ -124,7 +124,7 @@ const PGN_CASTLE: StrConst = r"(O-O(-O)?)";
 /// FEN patterns
 const FEN_RNK: StrConst = "([pnbrqkPNBRQK1-8]{1,8})";
 const FEN_SIDE: StrConst = "([bw])";
-const FEN_RIGHTS: StrConst = r"(-|([kqKQ]{1,4}))";
+const FEN_RIGHTS: StrConst = r"(-|([A-Ha-hkqKQ]{1,4}))";
 const FEN_EP: StrConst = r"(-|([a-h][1-8]))";
 
 /// UCI patterns
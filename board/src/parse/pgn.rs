@@ -23,7 +23,7 @@ pub fn moves<B: ChessBoard>(start: &B, encoded: &str) -> Result<Vec<Move>> {
     Ok(dest)
 }
 
-fn parse_single_move<B: ChessBoard>(start: &mut B, pgn_move: &str) -> Result<Move> {
+pub(crate) fn parse_single_move<B: ChessBoard>(start: &mut B, pgn_move: &str) -> Result<Move> {
     let legal = start.compute_moves(MoveComputeType::All);
     // If a castle move we can retrieve straight away
     if pgn_move == "O-O" {
@@ -98,6 +98,112 @@ fn parse_single_move<B: ChessBoard>(start: &mut B, pgn_move: &str) -> Result<Mov
     }
 }
 
+/// Encodes the given move in standard algebraic notation within the context
+/// of the given position which it must be legal in. The position is left
+/// unchanged but must be mutable as the move is temporarily made to determine
+/// whether it gives check or mate.
+pub fn to_san<B: ChessBoard>(board: &mut B, mv: &Move) -> Result<String> {
+    if board.hash() != mv.source() {
+        return Err(anyhow!("Mismatched source hash for {}", mv));
+    }
+    let mut dest = match *mv {
+        Move::Castle { zone, .. } => match zone {
+            CastleZone::WK | CastleZone::BK => "O-O".to_owned(),
+            CastleZone::WQ | CastleZone::BQ => "O-O-O".to_owned(),
+        },
+        Move::Enpassant { from, dest, .. } => format!("{}x{}", file_char(from), dest),
+        Move::Promotion {
+            from,
+            dest,
+            promoted,
+            capture,
+            ..
+        } => match capture {
+            None => format!("{}={}", dest, piece_char(promoted)),
+            Some(_) => format!("{}x{}={}", file_char(from), dest, piece_char(promoted)),
+        },
+        Move::Standard {
+            moving,
+            from,
+            dest,
+            capture,
+            ..
+        } => {
+            let capture_str = if capture.is_some() { "x" } else { "" };
+            if moving.is_pawn() {
+                match capture {
+                    None => dest.to_string(),
+                    Some(_) => format!("{}x{}", file_char(from), dest),
+                }
+            } else {
+                let legal = board.compute_moves(MoveComputeType::All);
+                format!(
+                    "{}{}{}{}",
+                    piece_char(moving),
+                    disambiguation(&legal, moving, from, dest),
+                    capture_str,
+                    dest
+                )
+            }
+        }
+    };
+    board.make(mv.clone())?;
+    if board.in_check() {
+        if board.compute_moves(MoveComputeType::All).is_empty() {
+            dest.push('#');
+        } else {
+            dest.push('+');
+        }
+    }
+    board.unmake()?;
+    Ok(dest)
+}
+
+/// Computes the minimal prefix needed to distinguish a piece move from
+/// other legal moves of the same piece type to the same square.
+fn disambiguation(legal: &[Move], moving: Piece, from: Square, dest: Square) -> String {
+    let others = legal
+        .iter()
+        .filter_map(|mv| match mv {
+            &Move::Standard {
+                moving: m,
+                from: f,
+                dest: d,
+                ..
+            } if m == moving && d == dest && f != from => Some(f),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if others.is_empty() {
+        String::new()
+    } else if others.iter().all(|sq| sq.file_index() != from.file_index()) {
+        file_char(from).to_string()
+    } else if others.iter().all(|sq| sq.rank_index() != from.rank_index()) {
+        rank_char(from).to_string()
+    } else {
+        from.to_string()
+    }
+}
+
+fn file_char(square: Square) -> char {
+    char_at(&square.to_string(), 0)
+}
+
+fn rank_char(square: Square) -> char {
+    char_at(&square.to_string(), 1)
+}
+
+fn piece_char(piece: Piece) -> char {
+    match piece {
+        Piece::WP | Piece::BP => 'P',
+        Piece::WN | Piece::BN => 'N',
+        Piece::WB | Piece::BB => 'B',
+        Piece::WR | Piece::BR => 'R',
+        Piece::WQ | Piece::BQ => 'Q',
+        Piece::WK | Piece::BK => 'K',
+    }
+}
+
 fn matches_square(file: Option<char>, rank: Option<char>, sq: Square) -> bool {
     let sq_str = sq.to_string();
     let matches_file = |f: char| char_at(&sq_str, 0) == f;
@@ -215,6 +321,126 @@ mod test {
     }
 }
 
+#[cfg(test)]
+mod test_san {
+    use crate::Board;
+
+    use super::*;
+
+    fn execute_test(expected: &'static str, fen: &'static str, mv: &'static str) -> Result<()> {
+        let mut board = fen.parse::<Board>()?;
+        let mv = Move::from(mv, board.hash())?;
+        assert_eq!(expected, to_san(&mut board, &mv)?.as_str());
+        assert_eq!(fen, board.to_fen().as_str());
+        Ok(())
+    }
+
+    #[test]
+    fn capture_with_check() -> Result<()> {
+        execute_test(
+            "Nxf2+",
+            "5r2/ppqkb1p1/2p1pr1p/3p4/3Pn2P/2NBR3/PPP1RPP1/7K b - - 1 21",
+            "sbne4f2wp",
+        )
+    }
+
+    #[test]
+    fn file_disambiguation() -> Result<()> {
+        execute_test(
+            "Rae8",
+            "r5r1/ppqkb1pp/2p1pn2/3p2B1/3P4/2NB1Q1P/PPP2PP1/4RRK1 b - - 8 14",
+            "sbra8e8-",
+        )
+    }
+
+    #[test]
+    fn rank_disambiguation() -> Result<()> {
+        execute_test(
+            "R1e2",
+            "4rr2/ppqkb1p1/2p1p2p/3p4/3Pn2B/2NBRQ1P/PPP2PP1/4R1K1 w - - 2 18",
+            "swre1e2-",
+        )
+    }
+
+    #[test]
+    fn square_disambiguation() -> Result<()> {
+        execute_test("Qh4e1+", "7k/8/8/8/4Q2Q/8/8/K6Q w - - 0 1", "swqh4e1-")
+    }
+
+    #[test]
+    fn checkmate() -> Result<()> {
+        execute_test(
+            "Rxf1#",
+            "5r2/ppqkb1p1/2p1p2p/3p4/P2P3P/2N1R3/1PP3P1/5B1K b - - 0 24",
+            "sbrf8f1wb",
+        )
+    }
+
+    #[test]
+    fn pawn_capture() -> Result<()> {
+        execute_test(
+            "dxc4",
+            "rnbqkbnr/ppp1pppp/8/3p4/2PP4/8/PP2PPPP/RNBQKBNR b KQkq c3 0 2",
+            "sbpd5c4wp",
+        )
+    }
+
+    #[test]
+    fn enpassant() -> Result<()> {
+        execute_test(
+            "exf6",
+            "r2qkbnr/pp1np1pp/2p5/3pPp2/8/2N2Q1P/PPPP1PP1/R1B1KB1R w KQkq f6 0 7",
+            "ewe5f6f5",
+        )
+    }
+
+    #[test]
+    fn promotion() -> Result<()> {
+        execute_test(
+            "fxg8=N+",
+            "r2q1bnr/pp1nkPpp/2p1p3/3p4/8/2N2Q1P/PPPP1PP1/R1B1KB1R w KQ - 1 9",
+            "pf7g8wnbn",
+        )?;
+        execute_test(
+            "f8=Q+",
+            "r2q2nr/pp1nkPpp/2p1p3/3p4/8/2N2Q1P/PPPP1PP1/R1B1KB1R w KQ - 1 9",
+            "pf7f8wq-",
+        )
+    }
+
+    #[test]
+    fn castling() -> Result<()> {
+        execute_test(
+            "O-O",
+            "r3k2r/pp1q1ppp/n1p2n2/4p3/3pP2P/3P1QP1/PPPN1PB1/R3K2R w KQkq - 1 13",
+            "cwk",
+        )?;
+        execute_test(
+            "O-O-O",
+            "r3k2r/pp1q1ppp/n1p2n2/4p3/3pP2P/3P1QP1/PPPN1PB1/R4RK1 b kq - 2 13",
+            "cbq",
+        )
+    }
+
+    #[test]
+    fn round_trip_game() -> Result<()> {
+        let pgn = "1.d4 Nf6 2.c4 g6 3.Nc3 Bg7 4.e4 d6 5.f3 O-O 6.Be3 Nbd7 7.Qd2 \
+            c5 8.d5 Ne5 9.h3 Nh5 10.Bf2 f5 11.exf5 Rxf5 12.g4 Rxf3 13.gxh5 \
+            Qf8 14.Ne4 Bh6 15.Qc2 Qf4 16.Ne2 Rxf2 17.Nxf2 Nf3+ 18.Kd1 Qh4 \
+            19.Nd3 Bf5 20.Nec1 Nd2 21.hxg6 hxg6 22.Bg2 Nxc4 23.Qf2 Ne3+ \
+            24.Ke2 Qc4 25.Bf3 Rf8 26.Rg1 Nc2 27.Kd1 Bxd3";
+        let mut board = crate::start();
+        let expected = pgn_move().find_iter(pgn).map(|m| m.as_str()).collect::<Vec<_>>();
+        let mut actual = vec![];
+        for mv in moves(&board, pgn)? {
+            actual.push(to_san(&mut board, &mv)?);
+            board.make(mv)?;
+        }
+        assert_eq!(expected, actual);
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test_single_move {
     use crate::Board;
@@ -119,9 +119,12 @@ pub fn single_move<B: ChessBoard>(start: &mut B, uci_move: &str) -> Result<Move>
         .find(|mv| match mv {
             &Move::Standard { from, dest, .. } => from == f && dest == d,
             &Move::Enpassant { from, dest, .. } => from == f && dest == d,
-            &Move::Castle { zone, .. } => {
-                let (_, king_src, king_dest) = zone.king_data();
-                f == king_src && d == king_dest
+            &Move::Castle { zone, castling, .. } => {
+                // The king takes rook form is accepted in standard games
+                // too but is the only form used in Chess960
+                let (_, king_src, king_dest) = castling.king_data(zone);
+                let (_, rook_src, _) = castling.rook_data(zone);
+                f == king_src && (d == rook_src || (!castling.is_chess960() && d == king_dest))
             }
             &Move::Promotion {
                 from,
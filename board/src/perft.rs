@@ -0,0 +1,233 @@
+use anyhow::{anyhow, Result};
+
+use crate::{ChessBoard, Move, MoveComputeType};
+
+/// Fixed size cache of subtree node counts keyed by the position hash
+/// and the depth the subtree was expanded to. Entries are simply
+/// overwritten on index collision.
+pub struct PerftTable {
+    inner: Vec<Option<(u64, usize, u64)>>,
+}
+
+impl PerftTable {
+    pub fn new(n_entries: usize) -> Result<PerftTable> {
+        if n_entries == 0 {
+            Err(anyhow!("Cannot create perft table with 0 entries"))
+        } else {
+            Ok(PerftTable {
+                inner: vec![None; n_entries],
+            })
+        }
+    }
+
+    fn get(&self, hash: u64, depth: usize) -> Option<u64> {
+        match self.inner[self.index(hash)] {
+            Some((h, d, count)) if h == hash && d == depth => Some(count),
+            _ => None,
+        }
+    }
+
+    fn insert(&mut self, hash: u64, depth: usize, count: u64) {
+        let index = self.index(hash);
+        self.inner[index] = Some((hash, depth, count));
+    }
+
+    fn index(&self, hash: u64) -> usize {
+        (hash % self.inner.len() as u64) as usize
+    }
+}
+
+/// Count the number of leaf nodes in the legal move tree rooted at the
+/// given position and expanded to the given depth. The board is restored
+/// to its original state before returning.
+pub fn perft<B: ChessBoard>(board: &mut B, depth: usize) -> Result<u64> {
+    perft_impl(board, depth, &mut None)
+}
+
+/// As with perft but subtree counts are cached in the given table which
+/// can dramatically reduce the work required for deeper searches.
+pub fn perft_cached<B: ChessBoard>(
+    board: &mut B,
+    depth: usize,
+    table: &mut PerftTable,
+) -> Result<u64> {
+    perft_impl(board, depth, &mut Some(table))
+}
+
+/// Compute the perft node count of the subtree reached by each legal move
+/// in the given position, useful for locating move generation bugs when
+/// compared against the output of a trusted engine. The moves are sorted
+/// by their uci representation.
+pub fn divide<B: ChessBoard>(board: &mut B, depth: usize) -> Result<Vec<(Move, u64)>> {
+    divide_impl(board, depth, &mut None)
+}
+
+/// As with divide but subtree counts are cached in the given table.
+pub fn divide_cached<B: ChessBoard>(
+    board: &mut B,
+    depth: usize,
+    table: &mut PerftTable,
+) -> Result<Vec<(Move, u64)>> {
+    divide_impl(board, depth, &mut Some(table))
+}
+
+fn divide_impl<B: ChessBoard>(
+    board: &mut B,
+    depth: usize,
+    table: &mut Option<&mut PerftTable>,
+) -> Result<Vec<(Move, u64)>> {
+    if depth == 0 {
+        return Err(anyhow!("Cannot divide at depth 0"));
+    }
+    let mut dest = vec![];
+    for mv in board.compute_moves(MoveComputeType::All) {
+        board.make(mv)?;
+        let count = perft_impl(board, depth - 1, table)?;
+        dest.push((board.unmake()?, count));
+    }
+    dest.sort_by_key(|(mv, _)| mv.uci_format());
+    Ok(dest)
+}
+
+fn perft_impl<B: ChessBoard>(
+    board: &mut B,
+    depth: usize,
+    table: &mut Option<&mut PerftTable>,
+) -> Result<u64> {
+    if depth == 0 {
+        return Ok(1);
+    }
+    let hash = board.hash();
+    if let Some(count) = table.as_ref().and_then(|t| t.get(hash, depth)) {
+        return Ok(count);
+    }
+    let moves = board.compute_moves(MoveComputeType::All);
+    let count = if depth == 1 {
+        moves.len() as u64
+    } else {
+        let mut count = 0;
+        for mv in moves {
+            board.make(mv)?;
+            count += perft_impl(board, depth - 1, table)?;
+            board.unmake()?;
+        }
+        count
+    };
+    if let Some(t) = table.as_mut() {
+        t.insert(hash, depth, count);
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Board;
+
+    use super::*;
+
+    /// Reference counts taken from https://www.chessprogramming.org/Perft_Results
+    fn execute_test(fen: &'static str, expected: &[u64]) -> Result<()> {
+        execute_board_test(fen.parse::<Board>()?, fen, expected)
+    }
+
+    fn execute_chess960_test(fen: &'static str, expected: &[u64]) -> Result<()> {
+        execute_board_test(Board::from_fen_chess960(fen)?, fen, expected)
+    }
+
+    fn execute_board_test(mut board: Board, fen: &'static str, expected: &[u64]) -> Result<()> {
+        let mut table = PerftTable::new(10_000)?;
+        for (i, &count) in expected.iter().enumerate() {
+            assert_eq!(count, perft(&mut board, i + 1)?, "depth {}", i + 1);
+            assert_eq!(count, perft_cached(&mut board, i + 1, &mut table)?);
+        }
+        assert_eq!(fen, board.to_fen());
+        Ok(())
+    }
+
+    #[test]
+    fn start_position() -> Result<()> {
+        execute_test(crate::STARTPOS_FEN, &[20, 400, 8902])
+    }
+
+    #[test]
+    fn kiwipete() -> Result<()> {
+        execute_test(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            &[48, 2039, 97862],
+        )
+    }
+
+    #[test]
+    fn position_3() -> Result<()> {
+        execute_test(
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            &[14, 191, 2812, 43238],
+        )
+    }
+
+    #[test]
+    fn position_4() -> Result<()> {
+        execute_test(
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            &[6, 264, 9467],
+        )
+    }
+
+    #[test]
+    fn position_5() -> Result<()> {
+        execute_test(
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+            &[44, 1486, 62379],
+        )
+    }
+
+    #[test]
+    fn chess960_position_1() -> Result<()> {
+        execute_chess960_test(
+            "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+            &[21, 528, 12189],
+        )
+    }
+
+    #[test]
+    fn chess960_position_2() -> Result<()> {
+        execute_chess960_test(
+            "2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9",
+            &[21, 807, 18002],
+        )
+    }
+
+    #[test]
+    fn chess960_position_3() -> Result<()> {
+        execute_chess960_test(
+            "b1q1rrkb/pppppppp/3nn3/8/P7/1PPP4/4PPPP/BQNNRKRB w GE - 1 9",
+            &[20, 479, 10471],
+        )
+    }
+
+    #[test]
+    fn chess960_position_4() -> Result<()> {
+        execute_chess960_test(
+            "qbbnnrkr/2pp2pp/p7/1p2pp2/8/P3PP2/1PPP1KPP/QBBNNR1R w hf - 0 9",
+            &[22, 593, 13440],
+        )
+    }
+
+    #[test]
+    fn chess960_position_5() -> Result<()> {
+        execute_chess960_test(
+            "1nbbnrkr/p1p1ppp1/3p4/1p3P1p/3Pq2P/8/PPP1P1P1/QNBBNRKR w HFhf - 0 9",
+            &[28, 1120, 31058],
+        )
+    }
+
+    #[test]
+    fn divide_start_position() -> Result<()> {
+        let mut board = crate::start();
+        let division = divide(&mut board, 2)?;
+        assert_eq!(20, division.len());
+        assert!(division.iter().all(|(_, count)| *count == 20));
+        assert_eq!("a2a3", division[0].0.uci_format());
+        Ok(())
+    }
+}
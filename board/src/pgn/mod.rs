@@ -0,0 +1,278 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error, Result};
+
+use crate::{Board, ChessBoard, Move};
+
+mod read;
+mod write;
+
+pub use read::read_games;
+
+/// The recorded outcome of a game, the unknown variant corresponds to
+/// the '*' termination marker used for games in progress or abandoned.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
+pub enum GameResult {
+    WhiteWin,
+    BlackWin,
+    Draw,
+    Unknown,
+}
+
+impl Display for GameResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameResult::WhiteWin => write!(f, "1-0"),
+            GameResult::BlackWin => write!(f, "0-1"),
+            GameResult::Draw => write!(f, "1/2-1/2"),
+            GameResult::Unknown => write!(f, "*"),
+        }
+    }
+}
+
+impl FromStr for GameResult {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1-0" => Ok(GameResult::WhiteWin),
+            "0-1" => Ok(GameResult::BlackWin),
+            "1/2-1/2" => Ok(GameResult::Draw),
+            "*" => Ok(GameResult::Unknown),
+            _ => Err(anyhow!("Cannot parse {} as a game result", s)),
+        }
+    }
+}
+
+/// A sequence of consecutive moves, either the main line of a game or a
+/// variation, with an optional comment preceding the first move.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Line {
+    pub comment: Option<String>,
+    pub nodes: Vec<Node>,
+}
+
+/// A single move within a line along with its annotations. Each variation
+/// is an alternative to this move and so starts from the position in which
+/// this move is made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node {
+    pub mv: Move,
+    pub nags: Vec<u8>,
+    pub comment: Option<String>,
+    pub variations: Vec<Line>,
+}
+
+impl Node {
+    pub fn new(mv: Move) -> Node {
+        Node {
+            mv,
+            nags: vec![],
+            comment: None,
+            variations: vec![],
+        }
+    }
+}
+
+/// A complete game record consisting of the tag pairs in the order they
+/// appeared, the annotated move text and the result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub tags: Vec<(String, String)>,
+    pub moves: Line,
+    pub result: GameResult,
+}
+
+impl FromStr for Game {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut games = read_games(s)?;
+        match games.len() {
+            1 => Ok(games.remove(0)),
+            n => Err(anyhow!("Expected exactly one game but found {}", n)),
+        }
+    }
+}
+
+impl Game {
+    /// Return the value of the first tag pair with the given name.
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Return the position the game started from, this is the position
+    /// given by the FEN tag if present or the standard start otherwise.
+    pub fn start(&self) -> Result<Board> {
+        start_position(&self.tags)
+    }
+
+    /// Return the moves of the main line in the order they were played.
+    pub fn main_line(&self) -> Vec<Move> {
+        self.moves.nodes.iter().map(|n| n.mv.clone()).collect()
+    }
+
+    /// Return the position reached at the end of the main line.
+    pub fn end(&self) -> Result<Board> {
+        let mut board = self.start()?;
+        for mv in self.main_line() {
+            board.make(mv)?;
+        }
+        Ok(board)
+    }
+
+    /// Encode this game in PGN export format, the output can be read back
+    /// to produce an identical game.
+    pub fn to_pgn(&self) -> Result<String> {
+        write::game(self)
+    }
+}
+
+fn start_position(tags: &[(String, String)]) -> Result<Board> {
+    match tags.iter().find(|(k, _)| k == "FEN") {
+        None => Ok(crate::start()),
+        Some((_, fen)) => fen.parse(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+
+    use crate::ChessBoard;
+
+    use super::*;
+
+    const FISCHER_SPASSKY: &'static str = r#"[Event "F/S Return Match"]
+[Site "Belgrade, Serbia JUG"]
+[Date "1992.11.04"]
+[Round "29"]
+[White "Fischer, Robert J."]
+[Black "Spassky, Boris V."]
+[Result "1/2-1/2"]
+
+1. e4 e5 2. Nf3 Nc6 3. Bb5 a6 {This opening is called the Ruy Lopez.} 4. Ba4 Nf6
+5. O-O Be7 6. Re1 b5 7. Bb3 d6 8. c3 O-O 9. h3 Nb8 10. d4 Nbd7 11. c4 c6 12.
+cxb5 axb5 13. Nc3 Bb7 14. Bg5 b4 15. Nb1 h6 16. Bh4 c5 17. dxe5 Nxe4 18. Bxe7
+Qxe7 19. exd6 Qf6 20. Nbd2 Nxd6 21. Nc4 Nxc4 22. Bxc4 Nb6 23. Ne5 Rae8 24. Bxf7+
+Rxf7 25. Nxf7 Rxe1+ 26. Qxe1 Kxf7 27. Qe3 Qg5 28. Qxg5 hxg5 29. b3 Ke6 30. a3
+Kd6 31. axb4 cxb4 32. Ra5 Nd5 33. f3 Bc8 34. Kf2 Bf5 35. Ra7 g6 36. Ra6+ Kc5 37.
+Ke1 Nf4 38. g3 Nxh3 39. Kd2 Kb5 40. Rd6 Kc5 41. Ra6 Nf2 42. g4 Bd3 43. Re6
+1/2-1/2
+"#;
+
+    const ANNOTATED: &'static str = r#"[Event "Annotated"]
+[Result "*"]
+
+{Starting comment} 1. e4 $1 e5 (1... c5 {Sicilian} 2. Nf3 (2. c3 d5) 2... d6)
+(1... e6) 2. Nf3 $14 {Developing} Nc6 *
+"#;
+
+    #[test]
+    fn read_tags_and_moves() -> Result<()> {
+        let game = FISCHER_SPASSKY.parse::<Game>()?;
+        assert_eq!(7, game.tags.len());
+        assert_eq!(Some("Fischer, Robert J."), game.tag("White"));
+        assert_eq!(Some("1992.11.04"), game.tag("Date"));
+        assert_eq!(GameResult::Draw, game.result);
+        assert_eq!(85, game.moves.nodes.len());
+        assert_eq!(
+            Some("This opening is called the Ruy Lopez."),
+            game.moves.nodes[5].comment.as_deref()
+        );
+        assert_eq!(
+            "8/8/4R1p1/2k3p1/1p4P1/1P1b1P2/3K1n2/8 b - - 2 43",
+            game.end()?.to_fen()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn write_reproduces_export_format() -> Result<()> {
+        let game = FISCHER_SPASSKY.parse::<Game>()?;
+        assert_eq!(FISCHER_SPASSKY, game.to_pgn()?);
+        Ok(())
+    }
+
+    #[test]
+    fn read_annotations_and_variations() -> Result<()> {
+        let game = ANNOTATED.parse::<Game>()?;
+        assert_eq!(GameResult::Unknown, game.result);
+        assert_eq!(Some("Starting comment"), game.moves.comment.as_deref());
+        let nodes = &game.moves.nodes;
+        assert_eq!(4, nodes.len());
+        assert_eq!(vec![1], nodes[0].nags);
+        assert_eq!(vec![14], nodes[2].nags);
+        assert_eq!(Some("Developing"), nodes[2].comment.as_deref());
+        let variations = &nodes[1].variations;
+        assert_eq!(2, variations.len());
+        assert_eq!("c7c5", variations[0].nodes[0].mv.uci_format());
+        assert_eq!(Some("Sicilian"), variations[0].nodes[0].comment.as_deref());
+        assert_eq!(
+            "c2c3",
+            variations[0].nodes[1].variations[0].nodes[0]
+                .mv
+                .uci_format()
+        );
+        assert_eq!("e7e6", variations[1].nodes[0].mv.uci_format());
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip_annotations_and_variations() -> Result<()> {
+        let game = ANNOTATED.parse::<Game>()?;
+        assert_eq!(ANNOTATED, game.to_pgn()?);
+        assert_eq!(game, game.to_pgn()?.parse::<Game>()?);
+        Ok(())
+    }
+
+    #[test]
+    fn read_from_custom_position() -> Result<()> {
+        let pgn = r#"[SetUp "1"]
+[FEN "r3k2r/pp1q1ppp/n1p2n2/4p3/3pP2P/3P1QP1/PPPN1PB1/R4RK1 b kq - 2 13"]
+
+13... O-O-O 14. a3 0-1"#;
+        let game = pgn.parse::<Game>()?;
+        assert_eq!(GameResult::BlackWin, game.result);
+        assert_eq!(
+            vec!["e8c8", "a2a3"],
+            game.main_line()
+                .iter()
+                .map(|m| m.uci_format())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(game, game.to_pgn()?.parse::<Game>()?);
+        Ok(())
+    }
+
+    #[test]
+    fn read_multiple_games() -> Result<()> {
+        let games = read_games(&format!("{}\n{}", FISCHER_SPASSKY, ANNOTATED))?;
+        assert_eq!(2, games.len());
+        assert_eq!(Some("Annotated"), games[1].tag("Event"));
+        Ok(())
+    }
+
+    #[test]
+    fn read_suffix_annotations() -> Result<()> {
+        let game = "1. e4!? e5?? 2. Qh5!! *".parse::<Game>()?;
+        let nags = game
+            .moves
+            .nodes
+            .iter()
+            .map(|n| n.nags.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(vec![vec![5], vec![4], vec![3]], nags);
+        Ok(())
+    }
+
+    #[test]
+    fn illegal_move_is_error() {
+        assert!("1. e4 e5 2. Ke3 *".parse::<Game>().is_err());
+        assert!("1. e4 (1. d4 *".parse::<Game>().is_err());
+    }
+}
@@ -0,0 +1,248 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
+use anyhow::{anyhow, Result};
+
+use crate::pgn::{start_position, Game, GameResult, Line, Node};
+use crate::{Board, ChessBoard, Move};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Tag(String, String),
+    Comment(String),
+    Nag(u8),
+    Move(String),
+    Result(GameResult),
+    Open,
+    Close,
+}
+
+/// Read all the games contained in the given PGN database string.
+pub fn read_games(pgn: &str) -> Result<Vec<Game>> {
+    let tokens = tokenize(pgn)?;
+    let mut tokens = tokens.into_iter().peekable();
+    let mut dest = vec![];
+    while tokens.peek().is_some() {
+        dest.push(read_game(&mut tokens)?);
+    }
+    Ok(dest)
+}
+
+fn read_game<I: Iterator<Item = Token>>(tokens: &mut Peekable<I>) -> Result<Game> {
+    let mut tags = vec![];
+    while let Some(Token::Tag(_, _)) = tokens.peek() {
+        if let Some(Token::Tag(name, value)) = tokens.next() {
+            tags.push((name, value));
+        }
+    }
+    let mut board = start_position(&tags)?;
+    let moves = read_line(tokens, &mut board)?;
+    let result = match tokens.next() {
+        Some(Token::Result(result)) => result,
+        None => match tags.iter().find(|(k, _)| k == "Result") {
+            Some((_, r)) => r.parse()?,
+            None => GameResult::Unknown,
+        },
+        Some(t) => return Err(anyhow!("Unexpected {:?} in main line", t)),
+    };
+    Ok(Game {
+        tags,
+        moves,
+        result,
+    })
+}
+
+/// Reads a line of moves from the given position, stopping before any
+/// token which closes the line. The board is left in the position before
+/// the final move of the line so that a following variation can be read.
+fn read_line<I: Iterator<Item = Token>>(
+    tokens: &mut Peekable<I>,
+    board: &mut Board,
+) -> Result<Line> {
+    let mut line = Line::default();
+    let mut pending: Option<Move> = None;
+    loop {
+        match tokens.peek() {
+            None | Some(Token::Close) | Some(Token::Result(_)) | Some(Token::Tag(_, _)) => {
+                return Ok(line)
+            }
+            _ => {}
+        }
+        match tokens.next().unwrap() {
+            Token::Comment(comment) => {
+                let dest = match line.nodes.last_mut() {
+                    None => &mut line.comment,
+                    Some(node) => &mut node.comment,
+                };
+                *dest = Some(match dest.take() {
+                    None => comment,
+                    Some(existing) => format!("{} {}", existing, comment),
+                });
+            }
+            Token::Nag(nag) => match line.nodes.last_mut() {
+                None => return Err(anyhow!("Annotation ${} precedes any move", nag)),
+                Some(node) => node.nags.push(nag),
+            },
+            Token::Move(san) => {
+                if let Some(mv) = pending.take() {
+                    board.make(mv)?;
+                }
+                let mv = crate::parse::pgn::parse_single_move(board, san.as_str())
+                    .map_err(|_| anyhow!("Illegal move {} at {}", san, board.to_fen()))?;
+                line.nodes.push(Node::new(mv.clone()));
+                pending = Some(mv);
+            }
+            Token::Open => {
+                let mut variation_board = board.clone();
+                let variation = read_line(tokens, &mut variation_board)?;
+                match (tokens.next(), line.nodes.last_mut()) {
+                    (Some(Token::Close), Some(node)) => node.variations.push(variation),
+                    (Some(Token::Close), None) => {
+                        return Err(anyhow!("Variation precedes any move"))
+                    }
+                    _ => return Err(anyhow!("Unterminated variation")),
+                }
+            }
+            t => return Err(anyhow!("Unexpected {:?} in move text", t)),
+        }
+    }
+}
+
+fn tokenize(pgn: &str) -> Result<Vec<Token>> {
+    let mut dest = vec![];
+    let mut chars = pgn.chars().peekable();
+    let mut line_start = true;
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => {
+                line_start = true;
+                continue;
+            }
+            c if c.is_whitespace() => {}
+            '%' if line_start => {
+                take_until(&mut chars, '\n');
+            }
+            '[' => dest.push(read_tag(take_until(&mut chars, ']').as_str())?),
+            '{' => dest.push(Token::Comment(
+                take_until(&mut chars, '}').trim().to_owned(),
+            )),
+            ';' => dest.push(Token::Comment(
+                take_until(&mut chars, '\n').trim().to_owned(),
+            )),
+            '(' => dest.push(Token::Open),
+            ')' => dest.push(Token::Close),
+            '$' => {
+                let nag = take_symbol(&mut chars, String::new());
+                dest.push(Token::Nag(
+                    nag.parse().map_err(|_| anyhow!("Bad NAG ${}", nag))?,
+                ));
+            }
+            c => {
+                let symbol = take_symbol(&mut chars, c.to_string());
+                dest.extend(read_symbol(symbol.as_str())?);
+            }
+        }
+        line_start = false;
+    }
+    Ok(dest)
+}
+
+fn take_until(chars: &mut Peekable<Chars>, end: char) -> String {
+    let mut dest = String::new();
+    let mut escaped = false;
+    for c in chars.by_ref() {
+        if c == end && !escaped {
+            break;
+        }
+        escaped = end == ']' && c == '\\' && !escaped;
+        dest.push(c);
+    }
+    dest
+}
+
+fn take_symbol(chars: &mut Peekable<Chars>, mut dest: String) -> String {
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() || "[]{}();$".contains(c) {
+            break;
+        }
+        dest.push(c);
+        chars.next();
+    }
+    dest
+}
+
+fn read_tag(content: &str) -> Result<Token> {
+    let content = content.trim();
+    let split = content
+        .find(char::is_whitespace)
+        .ok_or(anyhow!("Malformed tag [{}]", content))?;
+    let (name, value) = content.split_at(split);
+    let value = value.trim();
+    if value.len() < 2 || !value.starts_with('"') || !value.ends_with('"') {
+        return Err(anyhow!("Malformed tag value [{}]", content));
+    }
+    let value = value[1..value.len() - 1]
+        .replace("\\\"", "\"")
+        .replace("\\\\", "\\");
+    Ok(Token::Tag(name.to_owned(), value))
+}
+
+/// Converts a symbol from the move text into tokens, a symbol can be a
+/// result, a move number, a move or a move number immediately followed
+/// by a move with optional suffix annotations.
+fn read_symbol(symbol: &str) -> Result<Vec<Token>> {
+    if let Ok(result) = symbol.parse::<GameResult>() {
+        return Ok(vec![Token::Result(result)]);
+    }
+    let symbol = match symbol.rfind('.') {
+        Some(i) if symbol.starts_with(|c: char| c.is_ascii_digit()) => &symbol[i + 1..],
+        _ => symbol,
+    };
+    if symbol.is_empty() {
+        return Ok(vec![]);
+    }
+    let annotation_start = symbol.find(['!', '?']).unwrap_or(symbol.len());
+    let (san, annotation) = symbol.split_at(annotation_start);
+    let san = san.replace('0', "O").replace(['+', '#'], "");
+    let mut dest = vec![Token::Move(san)];
+    match annotation {
+        "" => {}
+        "!" => dest.push(Token::Nag(1)),
+        "?" => dest.push(Token::Nag(2)),
+        "!!" => dest.push(Token::Nag(3)),
+        "??" => dest.push(Token::Nag(4)),
+        "!?" => dest.push(Token::Nag(5)),
+        "?!" => dest.push(Token::Nag(6)),
+        _ => return Err(anyhow!("Unknown annotation {}", annotation)),
+    }
+    Ok(dest)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tokenize_move_text() -> Result<()> {
+        assert_eq!(
+            vec![
+                Token::Tag("White".to_owned(), "A \"B\" C".to_owned()),
+                Token::Comment("start".to_owned()),
+                Token::Move("e4".to_owned()),
+                Token::Nag(1),
+                Token::Open,
+                Token::Move("d4".to_owned()),
+                Token::Nag(13),
+                Token::Close,
+                Token::Move("e5".to_owned()),
+                Token::Comment("rest of line".to_owned()),
+                Token::Move("O-O".to_owned()),
+                Token::Result(GameResult::Draw),
+            ],
+            tokenize(
+                "[White \"A \\\"B\\\" C\"]\n{ start } 1.e4! (1. d4 $13) 1... e5 ; rest of line\n2. 0-0+ 1/2-1/2"
+            )?
+        );
+        Ok(())
+    }
+}
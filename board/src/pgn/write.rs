@@ -0,0 +1,80 @@
+use anyhow::Result;
+
+use crate::pgn::{Game, Line};
+use crate::{Board, ChessBoard, Side};
+
+/// Maximum line length of the move text in export format.
+const LINE_LENGTH: usize = 80;
+
+pub(super) fn game(game: &Game) -> Result<String> {
+    let mut dest = String::new();
+    for (name, value) in game.tags.iter() {
+        let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+        dest.push_str(format!("[{} \"{}\"]\n", name, escaped).as_str());
+    }
+    if !game.tags.is_empty() {
+        dest.push('\n');
+    }
+    let mut tokens = vec![];
+    line(&mut game.start()?, &game.moves, &mut tokens)?;
+    tokens.push(game.result.to_string());
+    dest.push_str(wrap(tokens).as_str());
+    dest.push('\n');
+    Ok(dest)
+}
+
+/// Writes the tokens for the given line which starts from the given position,
+/// the board is left in the position at the end of the line.
+fn line(board: &mut Board, line: &Line, dest: &mut Vec<String>) -> Result<()> {
+    let mut force_number = true;
+    if let Some(comment) = &line.comment {
+        dest.push(format!("{{{}}}", comment));
+    }
+    for node in line.nodes.iter() {
+        let move_number = board.position_count() / 2 + 1;
+        match board.active() {
+            Side::White => dest.push(format!("{}.", move_number)),
+            Side::Black if force_number => dest.push(format!("{}...", move_number)),
+            _ => {}
+        }
+        dest.push(node.mv.to_san(board)?);
+        dest.extend(node.nags.iter().map(|nag| format!("${}", nag)));
+        if let Some(comment) = &node.comment {
+            dest.push(format!("{{{}}}", comment));
+        }
+        for variation in node.variations.iter() {
+            let mut variation_tokens = vec![];
+            self::line(&mut board.clone(), variation, &mut variation_tokens)?;
+            match variation_tokens.len() {
+                0 => dest.push("()".to_owned()),
+                n => {
+                    variation_tokens[0].insert(0, '(');
+                    variation_tokens[n - 1].push(')');
+                    dest.extend(variation_tokens);
+                }
+            }
+        }
+        force_number = !node.variations.is_empty();
+        board.make(node.mv.clone())?;
+    }
+    Ok(())
+}
+
+/// Greedily joins the tokens into lines which do not exceed the maximum
+/// length unless a single token is itself too long.
+fn wrap(tokens: Vec<String>) -> String {
+    let mut dest = String::new();
+    let mut current_length = 0;
+    for token in tokens {
+        if current_length > 0 && current_length + 1 + token.len() > LINE_LENGTH {
+            dest.push('\n');
+            current_length = 0;
+        } else if current_length > 0 {
+            dest.push(' ');
+            current_length += 1;
+        }
+        current_length += token.len();
+        dest.push_str(token.as_str());
+    }
+    dest
+}
@@ -0,0 +1,186 @@
+use anyhow::{anyhow, Result};
+
+use crate::{Board, ChessBoard, Move};
+
+/// The uci encoding of a null move.
+const NULL_MOVE: &str = "0000";
+
+/// A record of a game which can be persisted and later used to rebuild
+/// an identical board. It holds the position before any moves were made
+/// along with the uci encoding of each move made since in order, null
+/// moves are recorded as "0000".
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameRecord {
+    pub start_fen: String,
+    pub moves: Vec<String>,
+}
+
+impl GameRecord {
+    /// Create the record of the given board.
+    pub fn of<B: ChessBoard>(board: &B) -> Result<GameRecord> {
+        let history = board.history()?;
+        Ok(GameRecord {
+            start_fen: history.start.to_fen(),
+            moves: history
+                .moves
+                .iter()
+                .map(|mv| mv.as_ref().map_or(NULL_MOVE.to_owned(), |m| m.uci_format()))
+                .collect(),
+        })
+    }
+
+    /// Rebuild the board this record was created from including the
+    /// history of moves made on it.
+    pub fn restore(&self) -> Result<Board> {
+        let mut board = self.start_fen.parse::<Board>()?;
+        self.play_moves(&mut board)?;
+        Ok(board)
+    }
+
+    /// Make each move in this record on the given board which must be in
+    /// the start position of the record.
+    pub fn replay<B: ChessBoard>(&self, board: &mut B) -> Result<()> {
+        if board.to_fen() != self.start_fen {
+            return Err(anyhow!(
+                "Board {} is not in start position {}",
+                board.to_fen(),
+                self.start_fen
+            ));
+        }
+        self.play_moves(board)
+    }
+
+    fn play_moves<B: ChessBoard>(&self, board: &mut B) -> Result<()> {
+        for mv in self.moves.iter() {
+            if mv == NULL_MOVE {
+                board.make_null()?;
+            } else {
+                let parsed = board.parse_uci(mv)?;
+                board.make(parsed)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The moves made on a board along with the position they were made
+/// from, iterating yields each position paired with the move which was
+/// made next, None represents a null move.
+#[derive(Debug, Clone)]
+pub struct GameHistory<B: ChessBoard> {
+    /// The position before any moves were made
+    pub start: B,
+    /// The moves made since the start position in order
+    pub moves: Vec<Option<Move>>,
+}
+
+impl<B: ChessBoard> IntoIterator for GameHistory<B> {
+    type Item = (B, Option<Move>);
+    type IntoIter = HistoryIter<B>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        HistoryIter {
+            board: self.start,
+            moves: self.moves.into_iter(),
+        }
+    }
+}
+
+pub struct HistoryIter<B: ChessBoard> {
+    board: B,
+    moves: std::vec::IntoIter<Option<Move>>,
+}
+
+impl<B: ChessBoard> Iterator for HistoryIter<B> {
+    type Item = (B, Option<Move>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mv = self.moves.next()?;
+        let position = self.board.clone();
+        let result = match &mv {
+            Some(m) => self.board.make(m.clone()),
+            None => self.board.make_null(),
+        };
+        // The moves were made from these positions originally
+        result.expect("Historical move could not be replayed");
+        Some((position, mv))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+
+    use crate::{Board, ChessBoard};
+
+    use super::*;
+
+    #[test]
+    fn restore_from_custom_start() -> Result<()> {
+        let fen = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 4 20";
+        let mut board = fen.parse::<Board>()?;
+        board.play_uci("e1g1 e8c8 f1f7")?;
+        board.make_null()?;
+        board.play_uci("f7f1 d8d1 f1d1 h8h1")?;
+        let record = GameRecord::of(&board)?;
+        assert_eq!(fen, record.start_fen);
+        assert_eq!(
+            vec!["e1g1", "e8c8", "f1f7", "0000", "f7f1", "d8d1", "f1d1", "h8h1"],
+            record.moves
+        );
+        let mut restored = record.restore()?;
+        assert_eq!(board.to_fen(), restored.to_fen());
+        assert_eq!(board.position_count(), restored.position_count());
+        assert_eq!(record, GameRecord::of(&restored)?);
+        for _ in 0..4 {
+            restored.unmake()?;
+        }
+        restored.unmake_null()?;
+        assert_eq!("2kr3r/5R2/8/8/8/8/8/R5K1 b - - 7 21", restored.to_fen());
+        Ok(())
+    }
+
+    #[test]
+    fn replay_requires_start_position() -> Result<()> {
+        let record = GameRecord {
+            start_fen: crate::STARTPOS_FEN.to_owned(),
+            moves: vec!["e2e4".to_owned()],
+        };
+        let mut board = crate::start();
+        board.play_uci("d2d4")?;
+        assert!(record.replay(&mut board).is_err());
+        let illegal = GameRecord {
+            moves: vec!["e2e5".to_owned()],
+            ..record
+        };
+        assert!(illegal.restore().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn iterate_history() -> Result<()> {
+        let mut board = crate::start();
+        board.play_uci("e2e4 e7e5 g1f3")?;
+        let positions = board
+            .history()?
+            .into_iter()
+            .map(|(position, mv)| (position.to_fen(), mv.map(|m| m.uci_format())))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (crate::STARTPOS_FEN.to_owned(), Some("e2e4".to_owned())),
+                (
+                    "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1".to_owned(),
+                    Some("e7e5".to_owned())
+                ),
+                (
+                    "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2".to_owned(),
+                    Some("g1f3".to_owned())
+                ),
+            ],
+            positions
+        );
+        Ok(())
+    }
+}
@@ -1,5 +1,4 @@
 use crate::eval::imp::EvalBoard;
-use crate::eval::WIN_VALUE;
 use crate::search::{search, SearchParameters};
 use crate::{Board, ChessBoard, Move};
 use regex::Regex;
@@ -137,11 +136,11 @@ fn benchmark() {
         if i % 5 == 0 {
             print_progress(case_count, err_count, search_duration.clone());
         }
-        match search(test_case.board.clone(), SearchParameters { terminator: depth, table_size }) {
+        match search(test_case.board.clone(), SearchParameters::new(depth, table_size)) {
             Err(message) => panic!("{}", message),
             Ok(outcome) => {
                 search_duration += outcome.time;
-                if test_case.expected_move != outcome.best_move || WIN_VALUE != outcome.eval {
+                if test_case.expected_move != outcome.best_move || outcome.mate_in() != Some(3) {
                     err_count += 1;
                     println!(
                         "Error at {}: Position {}, expected {}, actual {}",
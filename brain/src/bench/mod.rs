@@ -1,2 +1,3 @@
 mod mateinthree;
 mod middlegame;
+mod nullmove;
@@ -0,0 +1,71 @@
+use crate::search::pruning::NullMovePruning;
+use crate::search::SearchParameters;
+use crate::{Board, EvalBoard};
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::time::Duration;
+
+#[rustfmt::skip]
+/// Compares the depth reached with and without null move pruning when each
+/// position is given the same fixed amount of search time.
+/// ------------------------------------------------------------------------------------------------
+/// Date     | Millis  | Cases | Null move | Mean depth | Depth gains | Notes
+/// ------------------------------------------------------------------------------------------------
+/// 18/10/26 | 1000    | 50    | Disabled  | 4.52       | -           | Control run in release mode
+/// ------------------------------------------------------------------------------------------------
+/// 18/10/26 | 1000    | 50    | Enabled   | 5.18       | 29          | Default parameters, no
+///          |         |       |           |            |             | position searched shallower
+/// ------------------------------------------------------------------------------------------------
+#[test]
+#[ignore]
+fn benchmark() -> Result<(), Box<dyn Error>> {
+    dotenv::dotenv().ok();
+    let data_path = format!(
+        "{}/{}",
+        std::env::var("CARGO_MANIFEST_DIR").unwrap(),
+        std::env::var("NULLMOVE_INPUT_DATA").unwrap(),
+    );
+    let max_positions = std::env::var("NULLMOVE_MAX_CASES")?.parse::<usize>()?;
+    let millis = std::env::var("NULLMOVE_MILLIS")?.parse::<u64>()?;
+    let table_size = std::env::var("NULLMOVE_TABLE_SIZE")?.parse::<usize>()?;
+
+    let positions = BufReader::new(File::open(&data_path)?)
+        .lines()
+        .take(max_positions)
+        .map(|l| l.unwrap())
+        .map(|l| match EvalBoard::builder_fen(l.as_str()).map(|b| b.build()) {
+            Err(message) => panic!("{}", message),
+            Ok(position) => position,
+        })
+        .collect::<Vec<_>>();
+
+    let search_depth = |position: &EvalBoard<Board>, null_move_pruning: NullMovePruning| {
+        crate::search(position.clone(), SearchParameters {
+            null_move_pruning,
+            ..SearchParameters::new(Duration::from_millis(millis), table_size)
+        }).map(|outcome| outcome.depth)
+    };
+
+    let (mut control_total, mut pruned_total, mut gains, mut losses) = (0, 0, 0, 0);
+    for (i, position) in positions.iter().enumerate() {
+        if i % 5 == 0 {
+            println!("[Position {}]", i);
+        }
+        let control = search_depth(position, NullMovePruning::disabled())?;
+        let pruned = search_depth(position, NullMovePruning::default())?;
+        control_total += control;
+        pruned_total += pruned;
+        if pruned > control {
+            gains += 1;
+        } else if pruned < control {
+            losses += 1;
+        }
+    }
+    let cases = positions.len().max(1) as f64;
+    println!(
+        "Mean depth in {}ms over {} positions: disabled {:.2}, enabled {:.2} ({} deeper, {} shallower)",
+        millis, positions.len(), control_total as f64 / cases, pruned_total as f64 / cases, gains, losses
+    );
+    Ok(())
+}
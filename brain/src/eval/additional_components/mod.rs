@@ -29,4 +29,16 @@ impl EvalComponent for AdditionalEvalComponent {
             AdditionalEvalComponent::Opening(cmp) => cmp.unmake(mv),
         }
     }
+
+    fn make_null(&mut self) {
+        match self {
+            AdditionalEvalComponent::Opening(cmp) => cmp.make_null(),
+        }
+    }
+
+    fn unmake_null(&mut self) {
+        match self {
+            AdditionalEvalComponent::Opening(cmp) => cmp.unmake_null(),
+        }
+    }
 }
@@ -230,6 +230,14 @@ impl EvalComponent for OpeningComponent {
         };
         self.move_dist -= 1;
     }
+
+    fn make_null(&mut self) {
+        self.move_dist += 1;
+    }
+
+    fn unmake_null(&mut self) {
+        self.move_dist -= 1;
+    }
 }
 
 #[cfg(test)]
@@ -254,6 +262,29 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn null_move_advances_move_distance() -> Result<()> {
+        let mut board = crate::start();
+        let mut component = OpeningComponent::new(dummy_rewards());
+        for uci_mv in &["e2e4", "d7d5"] {
+            let mv = board.parse_uci(uci_mv)?;
+            component.make(&mv);
+            board.make(mv)?;
+        }
+        component.make_null();
+        board.make_null()?;
+        assert_eq!(3, component.move_dist);
+        // Black captures the e pawn, it must be restored when unmade
+        let capture = board.parse_uci("d5e4")?;
+        component.make(&capture);
+        assert_eq!(Some(4), component.pieces.w_e_pawn.capture_dist);
+        component.unmake(&capture);
+        assert_eq!(None, component.pieces.w_e_pawn.capture_dist);
+        component.unmake_null();
+        assert_eq!(2, component.move_dist);
+        Ok(())
+    }
+
     fn dummy_rewards() -> OpeningRewards {
         OpeningRewards {
             d_pawn: 1,
@@ -5,8 +5,8 @@ use crate::eval::{EvalChessBoard, EvalComponent};
 use crate::{eval, Board, PieceValues, PositionTables};
 use anyhow::Result;
 use myopic_board::{
-    BitBoard, CastleZone, ChessBoard, FenComponent, Move, MoveComputeType, Piece,
-    Side, Square, Termination,
+    BitBoard, CastleZone, Castling, ChessBoard, FenComponent, Move, MoveComputeType, MoveList,
+    Piece, Side, Square, Termination,
 };
 use crate::enumset::EnumSet;
 
@@ -94,10 +94,48 @@ impl<B: ChessBoard> ChessBoard for EvalBoard<B> {
         Ok(action)
     }
 
+    fn make_null(&mut self) -> Result<()> {
+        self.board.make_null()?;
+        self.material.make_null();
+        for cmp in self.cmps.iter_mut() {
+            cmp.make_null();
+        }
+        Ok(())
+    }
+
+    fn unmake_null(&mut self) -> Result<()> {
+        self.board.unmake_null()?;
+        self.material.unmake_null();
+        for cmp in self.cmps.iter_mut() {
+            cmp.unmake_null();
+        }
+        Ok(())
+    }
+
     fn compute_moves(&mut self, computation_type: MoveComputeType) -> Vec<Move> {
         self.board.compute_moves(computation_type)
     }
 
+    fn compute_moves_into(&mut self, computation_type: MoveComputeType, dest: &mut MoveList) {
+        self.board.compute_moves_into(computation_type, dest)
+    }
+
+    fn moves_from(&mut self, square: Square) -> Vec<Move> {
+        self.board.moves_from(square)
+    }
+
+    fn moves_to(&mut self, square: Square) -> Vec<Move> {
+        self.board.moves_to(square)
+    }
+
+    fn is_pseudo_legal(&self, action: &Move) -> bool {
+        self.board.is_pseudo_legal(action)
+    }
+
+    fn is_legal(&mut self, action: &Move) -> bool {
+        self.board.is_legal(action)
+    }
+
     fn termination_status(&mut self) -> Option<Termination> {
         self.board.termination_status()
     }
@@ -106,6 +144,26 @@ impl<B: ChessBoard> ChessBoard for EvalBoard<B> {
         self.board.in_check()
     }
 
+    fn gives_check(&mut self, action: &Move) -> bool {
+        self.board.gives_check(action)
+    }
+
+    fn attackers_to(&mut self, square: Square, side: Side) -> BitBoard {
+        self.board.attackers_to(square, side)
+    }
+
+    fn checkers(&mut self) -> BitBoard {
+        self.board.checkers()
+    }
+
+    fn pinned_pieces(&mut self, side: Side) -> BitBoard {
+        self.board.pinned_pieces(side)
+    }
+
+    fn discovered_check_candidates(&mut self) -> BitBoard {
+        self.board.discovered_check_candidates()
+    }
+
     fn side(&self, side: Side) -> BitBoard {
         self.board.side(side)
     }
@@ -118,6 +176,18 @@ impl<B: ChessBoard> ChessBoard for EvalBoard<B> {
         self.board.hash()
     }
 
+    fn hash_after(&self, action: &Move) -> u64 {
+        self.board.hash_after(action)
+    }
+
+    fn pawn_hash(&self) -> u64 {
+        self.board.pawn_hash()
+    }
+
+    fn material_key(&self) -> u64 {
+        self.board.material_key()
+    }
+
     fn active(&self) -> Side {
         self.board.active()
     }
@@ -138,6 +208,10 @@ impl<B: ChessBoard> ChessBoard for EvalBoard<B> {
         self.board.piece(location)
     }
 
+    fn move_history(&self) -> Vec<Option<Move>> {
+        self.board.move_history()
+    }
+
     fn half_move_clock(&self) -> usize {
         self.board.half_move_clock()
     }
@@ -150,6 +224,10 @@ impl<B: ChessBoard> ChessBoard for EvalBoard<B> {
         self.board.remaining_rights()
     }
 
+    fn castling(&self) -> Castling {
+        self.board.castling()
+    }
+
     fn play_pgn(&mut self, moves: &str) -> Result<Vec<Move>> {
         let parsed_moves = self.board.play_pgn(moves)?;
         for mv in parsed_moves.iter() {
@@ -184,7 +262,7 @@ impl<B: ChessBoard> ChessBoard for EvalBoard<B> {
 impl<B: ChessBoard> EvalChessBoard for EvalBoard<B> {
     fn static_eval(&mut self) -> i32 {
         match self.termination_status() {
-            Some(Termination::Draw) => eval::DRAW_VALUE,
+            Some(Termination::Draw(_)) => eval::DRAW_VALUE,
             Some(Termination::Loss) => eval::LOSS_VALUE,
             None => {
                 let eval = self.material.static_eval()
@@ -211,7 +289,7 @@ impl<B: ChessBoard> EvalChessBoard for EvalBoard<B> {
 #[cfg(test)]
 mod test {
     use crate::eval::imp::EvalBoard;
-    use crate::eval::material;
+    use crate::eval::{material, EvalChessBoard};
     use crate::{Board, PieceValues, PositionTables};
     use myopic_board::{
         ChessBoard, Reflectable, UciMove,
@@ -302,4 +380,18 @@ mod test {
             ],
         );
     }
+
+    #[test]
+    fn null_move() {
+        let mut board = EvalBoard::start();
+        board.play_uci("e2e4 e7e5 g1f3").unwrap();
+        let (hash, eval) = (board.hash(), board.static_eval());
+        board.make_null().unwrap();
+        assert_eq!(-eval, board.static_eval());
+        board.play_uci("f3g5").unwrap();
+        board.unmake().unwrap();
+        board.unmake_null().unwrap();
+        assert_eq!(hash, board.hash());
+        assert_eq!(eval, board.static_eval());
+    }
 }
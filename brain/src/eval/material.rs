@@ -75,9 +75,9 @@ impl EvalComponent for Material {
                 self.remove(active_pawn.reflect(), capture);
             }
 
-            &Move::Castle { zone, .. } => {
-                let (rook, r_src, r_target) = zone.rook_data();
-                let (king, k_src, k_target) = zone.king_data();
+            &Move::Castle { zone, castling, .. } => {
+                let (rook, r_src, r_target) = castling.rook_data(zone);
+                let (king, k_src, k_target) = castling.king_data(zone);
                 self.remove(rook, r_src);
                 self.add(rook, r_target);
                 self.remove(king, k_src);
@@ -126,9 +126,9 @@ impl EvalComponent for Material {
                 self.add(active_pawn, from);
                 self.add(passive_pawn, capture);
             }
-            &Move::Castle { zone, .. } => {
-                let (rook, r_src, r_target) = zone.rook_data();
-                let (king, k_src, k_target) = zone.king_data();
+            &Move::Castle { zone, castling, .. } => {
+                let (rook, r_src, r_target) = castling.rook_data(zone);
+                let (king, k_src, k_target) = castling.king_data(zone);
                 self.add(rook, r_src);
                 self.remove(rook, r_target);
                 self.add(king, k_src);
@@ -24,6 +24,59 @@ pub const LOSS_VALUE: i32 = -WIN_VALUE;
 /// The evaluation assigned to a drawn position.
 pub const DRAW_VALUE: i32 = 0;
 
+/// Checkmate scores lie within this many half moves of the win and
+/// loss values, no static evaluation can get this close.
+const MATE_PLY_BOUND: i32 = 1_000;
+
+/// The evaluation of a position in which the side to move is checkmated
+/// the given number of half moves from the root of the search, so that
+/// a quicker mate is preferred by the winning side.
+pub fn loss_in(ply: usize) -> i32 {
+    LOSS_VALUE + ply as i32
+}
+
+/// Whether the evaluation represents a forced checkmate for either side.
+pub fn is_mate(eval: i32) -> bool {
+    eval.abs() > WIN_VALUE - MATE_PLY_BOUND
+}
+
+/// The number of moves until checkmate for an evaluation taken at the
+/// root of a search. It is positive if the side to move delivers mate
+/// and negative if they are mated.
+pub fn mate_in(eval: i32) -> Option<i32> {
+    if is_mate(eval) {
+        let moves = (WIN_VALUE - eval.abs() + 1) / 2;
+        Some(eval.signum() * moves)
+    } else {
+        None
+    }
+}
+
+/// Checkmate scores are relative to the root of the search but a position
+/// can be reached at different plies, convert an evaluation made at the
+/// given ply to be relative to that position instead so it can be stored.
+pub fn to_node_relative(eval: i32, ply: usize) -> i32 {
+    if !is_mate(eval) {
+        eval
+    } else if eval > 0 {
+        eval + ply as i32
+    } else {
+        eval - ply as i32
+    }
+}
+
+/// Reverse [to_node_relative] for an evaluation of a position reached at
+/// the given ply.
+pub fn to_root_relative(eval: i32, ply: usize) -> i32 {
+    if !is_mate(eval) {
+        eval
+    } else if eval > 0 {
+        eval - ply as i32
+    } else {
+        eval + ply as i32
+    }
+}
+
 /// Extension of the Board trait which adds a static evaluation function.
 pub trait EvalChessBoard: ChessBoard {
     /// The static evaluation function assigns a score to this exact
@@ -52,6 +105,13 @@ pub trait EvalComponent: Clone + Send {
     fn make(&mut self, mv: &Move);
 
     fn unmake(&mut self, mv: &Move);
+
+    /// Update this component for the active side passing the turn, the
+    /// pieces do not move so by default nothing changes.
+    fn make_null(&mut self) {}
+
+    /// Reverse a previous call to make_null.
+    fn unmake_null(&mut self) {}
 }
 
 /// Allows one to configure the parameters of the evaluation board.
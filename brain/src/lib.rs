@@ -20,6 +20,7 @@ pub use myopic_board::*;
 
 pub use search::interactive;
 pub use search::negascout;
+pub use search::pruning::{LateMoveReductions, NullMovePruning};
 pub use search::search;
 pub use search::terminator::SearchTerminator;
 pub use search::SearchOutcome;
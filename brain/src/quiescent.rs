@@ -1,5 +1,6 @@
+use crate::eval;
 use crate::eval::EvalChessBoard;
-use crate::{eval, see};
+use crate::search::movepicker::{score_capture, MoveListPool, ScoredMoves};
 use anyhow::Result;
 use myopic_board::{BitBoard, Move, MoveComputeType, Reflectable, Termination};
 use std::cmp;
@@ -10,17 +11,21 @@ const Q_CHECK_CAP: i32 = -2;
 // TODO Do we want the quiescent search to have interruption finishing checks too?
 
 /// Performs a depth limited search looking to evaluate only quiet positions,
-/// i.e. those with no attack moves.
+/// i.e. those with no attack moves. The move lists for each node are taken
+/// from the given pool.
 pub fn search<B: EvalChessBoard>(
     state: &mut B,
     mut alpha: i32,
     beta: i32,
     depth: i32,
+    pool: &mut MoveListPool,
 ) -> Result<i32> {
     if depth == Q_DEPTH_CAP || state.termination_status().is_some() {
+        // The search starts at depth -1 so the distance to a checkmate
+        // is measured in half moves from the first call
         return Ok(match state.termination_status() {
-            Some(Termination::Loss) => eval::LOSS_VALUE,
-            Some(Termination::Draw) => eval::DRAW_VALUE,
+            Some(Termination::Loss) => eval::loss_in((-1 - depth) as usize),
+            Some(Termination::Draw(_)) => eval::DRAW_VALUE,
             None => state.static_eval(),
         });
     }
@@ -44,57 +49,42 @@ pub fn search<B: EvalChessBoard>(
         alpha = result;
     }
 
-    for evolve in compute_quiescent_moves(state, depth) {
+    let mut moves = pool.take();
+    compute_quiescent_moves(state, depth, &mut moves);
+    while let Some((evolve, _)) = moves.pop_best() {
         state.make(evolve)?;
-        let next_result = -search(state, -beta, -alpha, depth - 1)?;
+        let next_result = -search(state, -beta, -alpha, depth - 1, pool)?;
         state.unmake()?;
         result = cmp::max(result, next_result);
         alpha = cmp::max(alpha, result);
         if alpha > beta {
+            pool.recycle(moves);
             return Ok(beta);
         }
     }
+    pool.recycle(moves);
     return Ok(result);
 }
 
-fn compute_quiescent_moves<B: EvalChessBoard>(state: &mut B, depth: i32) -> Vec<Move> {
-    let mut moves = if depth < Q_CHECK_CAP {
-        state.compute_moves(MoveComputeType::Attacks)
+/// Non attacking moves (checks or evasions) are searched first followed by
+/// the attacks in descending order of exchange value.
+fn compute_quiescent_moves<B: EvalChessBoard>(state: &mut B, depth: i32, dest: &mut ScoredMoves) {
+    let computation_type = if depth < Q_CHECK_CAP {
+        MoveComputeType::Attacks
     } else {
-        state.compute_moves(MoveComputeType::AttacksChecks)
+        MoveComputeType::AttacksChecks
     };
     let enemies = state.side(state.active().reflect());
-    let is_attack_filter = |mv: &Move| is_attack(mv, enemies);
-    // If in check don't filter out any attacks, we must check all available moves.
-    let good_attack_threshold = if state.in_check() { -eval::INFTY } else { 0 };
-    let split_index = itertools::partition(&mut moves, is_attack_filter);
-    // Score attacks using see and filter bad exchanges before sorting and
-    // recombining.
-    let mut attacks: Vec<_> = moves
-        .iter()
-        .take(split_index)
-        .map(|mv| (mv, score_attack(state, mv)))
-        .filter(|(_, score)| *score > good_attack_threshold)
-        .collect();
-    attacks.sort_by_key(|(_, score)| -*score);
-
-    moves
-        .iter()
-        .cloned()
-        .skip(split_index)
-        .chain(attacks.into_iter().map(|(mv, _)| mv.clone()))
-        .collect()
-}
-
-fn score_attack<B: EvalChessBoard>(state: &mut B, attack: &Move) -> i32 {
-    match attack {
-        &Move::Enpassant { .. } => 10000,
-        &Move::Promotion { .. } => 20000,
-        &Move::Standard { from, dest, .. } => {
-            see::exchange_value(state, from, dest, state.piece_values())
+    dest.fill(state, computation_type, |board, mv| {
+        if is_attack(mv, enemies) {
+            score_capture(board, mv)
+        } else {
+            i32::MAX
         }
-        // Should never get here
-        _ => 0,
+    });
+    // If in check don't filter out any attacks, we must check all available moves.
+    if !state.in_check() {
+        dest.remove_where(|_, score| score <= 0, None);
     }
 }
 
@@ -0,0 +1,237 @@
+use myopic_board::{CompactMove, Move, Side};
+
+/// The magnitude which history scores approach but never exceed.
+const HISTORY_MAX: i32 = 16_384;
+
+/// Dynamic move ordering state which is built up from the beta cutoffs
+/// found during an iterative deepening search, it is shared by all the
+/// iterations of a search.
+#[derive(Default)]
+pub struct SearchHistory {
+    pub butterfly: HistoryTable,
+    pub killers: KillerTable,
+    pub countermoves: CountermoveTable,
+}
+
+impl SearchHistory {
+    /// Update every table after the given quiet move caused a beta cutoff
+    /// in a node at the given ply and depth. The quiet moves searched in
+    /// the node before the cutoff and the move made to reach the node,
+    /// if known, are also required.
+    pub fn record_cutoff(
+        &mut self,
+        ply: usize,
+        depth: usize,
+        previous: Option<CompactMove>,
+        cutoff: &Move,
+        searched: &[Move],
+    ) {
+        self.butterfly.update(cutoff, searched, depth);
+        self.killers.update(ply, cutoff);
+        if let Some(previous) = previous {
+            self.countermoves.update(previous, cutoff);
+        }
+    }
+}
+
+/// Butterfly history table recording how often each quiet move, keyed by
+/// the side making it and its origin and destination squares, has caused
+/// a beta cutoff during the search. Scores decay towards zero when moves
+/// which were searched fail to cause a cutoff.
+pub struct HistoryTable {
+    scores: Vec<i32>,
+}
+
+impl Default for HistoryTable {
+    fn default() -> Self {
+        HistoryTable {
+            scores: vec![0; 2 * 64 * 64],
+        }
+    }
+}
+
+impl HistoryTable {
+    /// The history score of the given move, only standard quiet moves
+    /// are tracked and all others score zero.
+    pub fn score(&self, mv: &Move) -> i32 {
+        index(mv).map(|i| self.scores[i]).unwrap_or(0)
+    }
+
+    /// Reward the quiet move which caused a cutoff at the given depth and
+    /// penalise the quiet moves which were searched before it.
+    pub fn update(&mut self, cutoff: &Move, searched: &[Move], depth: usize) {
+        let bonus = (depth * depth).min(HISTORY_MAX as usize) as i32;
+        self.apply(cutoff, bonus);
+        for mv in searched.iter().filter(|&m| m != cutoff) {
+            self.apply(mv, -bonus);
+        }
+    }
+
+    fn apply(&mut self, mv: &Move, bonus: i32) {
+        if let Some(i) = index(mv) {
+            // Scale the change down as the score saturates so it remains
+            // bounded by the maximum
+            let score = &mut self.scores[i];
+            *score += bonus - *score * bonus.abs() / HISTORY_MAX;
+        }
+    }
+}
+
+/// Two killer moves for each ply of the search, these are the quiet moves
+/// which most recently caused a beta cutoff in a node at that ply and so
+/// are likely to refute the moves made in sibling nodes too.
+#[derive(Default)]
+pub struct KillerTable {
+    killers: Vec<[Option<Move>; 2]>,
+}
+
+impl KillerTable {
+    /// The killers at the given ply, most recent first.
+    pub fn get(&self, ply: usize) -> [Option<Move>; 2] {
+        self.killers.get(ply).cloned().unwrap_or([None, None])
+    }
+
+    /// Record a cutoff move at the given ply, it displaces the older of
+    /// the two killers unless it is already the most recent.
+    pub fn update(&mut self, ply: usize, mv: &Move) {
+        if self.killers.len() <= ply {
+            self.killers.resize(ply + 1, [None, None]);
+        }
+        let slots = &mut self.killers[ply];
+        if slots[0].as_ref() != Some(mv) {
+            slots[1] = slots[0].take();
+            slots[0] = Some(mv.clone());
+        }
+    }
+}
+
+/// The quiet move which most recently refuted each move, keyed by the
+/// origin and destination squares of the move being refuted.
+pub struct CountermoveTable {
+    moves: Vec<Option<Move>>,
+}
+
+impl Default for CountermoveTable {
+    fn default() -> Self {
+        CountermoveTable {
+            moves: vec![None; 64 * 64],
+        }
+    }
+}
+
+impl CountermoveTable {
+    /// The last recorded refutation of the given move.
+    pub fn get(&self, previous: CompactMove) -> Option<Move> {
+        self.moves[countermove_index(previous)].clone()
+    }
+
+    pub fn update(&mut self, previous: CompactMove, refutation: &Move) {
+        self.moves[countermove_index(previous)] = Some(refutation.clone());
+    }
+}
+
+fn countermove_index(mv: CompactMove) -> usize {
+    (mv.origin() as usize) * 64 + mv.dest() as usize
+}
+
+/// Whether the move is a standard move which doesn't capture, these are
+/// the moves ordered and reduced according to their history.
+pub fn is_quiet(mv: &Move) -> bool {
+    match mv {
+        Move::Standard { capture, .. } => capture.is_none(),
+        _ => false,
+    }
+}
+
+fn index(mv: &Move) -> Option<usize> {
+    match mv {
+        &Move::Standard {
+            moving,
+            from,
+            dest,
+            capture: None,
+            ..
+        } => {
+            let side = match moving.side() {
+                Side::White => 0,
+                Side::Black => 1,
+            };
+            Some(side * 64 * 64 + (from as usize) * 64 + dest as usize)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{HistoryTable, SearchHistory, HISTORY_MAX};
+    use myopic_board::{CompactMove, Move, Piece, Square};
+
+    fn quiet(moving: Piece, from: Square, dest: Square) -> Move {
+        Move::Standard {
+            source: 0,
+            moving,
+            from,
+            dest,
+            capture: None,
+        }
+    }
+
+    #[test]
+    fn cutoff_rewarded_and_others_penalised() {
+        let mut table = HistoryTable::default();
+        let cutoff = quiet(Piece::WN, Square::G1, Square::F3);
+        let searched = quiet(Piece::WP, Square::A2, Square::A3);
+        table.update(&cutoff, &[searched.clone(), cutoff.clone()], 4);
+        assert_eq!(16, table.score(&cutoff));
+        assert_eq!(-16, table.score(&searched));
+        // The same action for the other side is tracked separately
+        assert_eq!(0, table.score(&quiet(Piece::BN, Square::G1, Square::F3)));
+    }
+
+    #[test]
+    fn captures_are_not_tracked() {
+        let mut table = HistoryTable::default();
+        let capture = Move::Standard {
+            source: 0,
+            moving: Piece::WQ,
+            from: Square::D1,
+            dest: Square::D8,
+            capture: Some(Piece::BQ),
+        };
+        table.update(&capture, &[], 5);
+        assert_eq!(0, table.score(&capture));
+    }
+
+    #[test]
+    fn scores_remain_bounded() {
+        let mut table = HistoryTable::default();
+        let cutoff = quiet(Piece::WN, Square::G1, Square::F3);
+        for _ in 0..1000 {
+            table.update(&cutoff, &[], 20);
+        }
+        let score = table.score(&cutoff);
+        assert!(0 < score && score <= HISTORY_MAX, "{}", score);
+    }
+
+    #[test]
+    fn cutoff_updates_killers_and_countermoves() {
+        let mut history = SearchHistory::default();
+        let previous = quiet(Piece::BP, Square::E7, Square::E5);
+        let (first, second) = (
+            quiet(Piece::WN, Square::G1, Square::F3),
+            quiet(Piece::WB, Square::F1, Square::C4),
+        );
+        history.record_cutoff(2, 3, Some(CompactMove::from(&previous)), &first, &[]);
+        history.record_cutoff(2, 3, None, &second, &[]);
+        // Recording the most recent killer again doesn't evict the other
+        history.record_cutoff(2, 3, None, &second, &[]);
+        assert_eq!([Some(second), Some(first.clone())], history.killers.get(2));
+        assert_eq!([None, None], history.killers.get(1));
+        assert_eq!([None, None], history.killers.get(10));
+        assert_eq!(
+            Some(first),
+            history.countermoves.get(CompactMove::from(&previous))
+        );
+    }
+}
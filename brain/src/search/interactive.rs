@@ -2,6 +2,7 @@ use crate::search::{search as blocking_search, SearchContext, SearchParameters,
 use crate::{EvalChessBoard, SearchOutcome};
 use anyhow::Result;
 use myopic_board::Side;
+use std::cell::Cell;
 use std::cmp::{max, min};
 use std::rc::Rc;
 use std::sync::mpsc;
@@ -14,6 +15,10 @@ const DEFAULT_SEARCH_DURATION: Duration = Duration::from_secs(30);
 const DEFAULT_SEARCH_DEPTH: usize = 10;
 const DEFAULT_TABLE_SIZE: usize = 100_000;
 const MAX_COMPUTED_MOVE_SEARCH_DURATION: Duration = Duration::from_secs(45);
+/// Each fail low extends the search time by the max time divided by this
+const FAIL_LOW_EXTENSION_DIVISOR: u32 = 4;
+/// The search time is extended by at most the max time divided by this
+const MAX_FAIL_LOW_EXTENSION_DIVISOR: u32 = 2;
 
 pub type SearchCommandTx<B> = Sender<SearchCommand<B>>;
 pub type SearchResultRx = Receiver<Result<SearchOutcome>>;
@@ -136,14 +141,12 @@ impl<B: EvalChessBoard + 'static> InteractiveSearch<B> {
         let tracker = InteractiveSearchTerminator {
             max_depth: self.max_depth,
             max_time: self.max_time,
+            fail_low_extension: Cell::new(Duration::from_secs(0)),
             stop_signal: self.input_rx.clone(),
         };
         blocking_search(
             self.root.clone().unwrap(),
-            SearchParameters {
-                terminator: tracker,
-                table_size: self.transposition_table_size,
-            },
+            SearchParameters::new(tracker, self.transposition_table_size),
         )
     }
 }
@@ -151,16 +154,57 @@ impl<B: EvalChessBoard + 'static> InteractiveSearch<B> {
 struct InteractiveSearchTerminator<B: EvalChessBoard> {
     max_time: Duration,
     max_depth: usize,
+    /// Extra time granted to the search because the eval
+    /// dropped between iterations
+    fail_low_extension: Cell<Duration>,
     stop_signal: Rc<CmdRx<B>>,
 }
 
 impl<B: EvalChessBoard> SearchTerminator for InteractiveSearchTerminator<B> {
     fn should_terminate(&self, ctx: &SearchContext) -> bool {
-        ctx.start_time.elapsed() > self.max_time
+        ctx.start_time.elapsed() > self.max_time + self.fail_low_extension.get()
             || ctx.depth_remaining >= self.max_depth
             || match self.stop_signal.try_recv() {
                 Ok(SearchCommand::Stop) => true,
                 _ => false,
             }
     }
+
+    fn on_fail_low(&self, _depth: usize, _previous_eval: i32, _upper_bound: i32) {
+        let extension = self.fail_low_extension.get() + self.max_time / FAIL_LOW_EXTENSION_DIVISOR;
+        self.fail_low_extension.set(min(
+            extension,
+            self.max_time / MAX_FAIL_LOW_EXTENSION_DIVISOR,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{InteractiveSearchTerminator, SearchCommand};
+    use crate::search::SearchTerminator;
+    use crate::EvalBoard;
+    use myopic_board::Board;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    #[test]
+    fn fail_low_extends_search_time() {
+        let (_tx, rx) = mpsc::channel::<SearchCommand<EvalBoard<Board>>>();
+        let terminator = InteractiveSearchTerminator {
+            max_time: Duration::from_millis(1000),
+            max_depth: 10,
+            fail_low_extension: Cell::new(Duration::from_secs(0)),
+            stop_signal: Rc::new(rx),
+        };
+        let extension = &terminator.fail_low_extension;
+        terminator.on_fail_low(4, 100, 50);
+        assert_eq!(Duration::from_millis(250), extension.get());
+        for _ in 0..5 {
+            terminator.on_fail_low(4, 100, 50);
+        }
+        assert_eq!(Duration::from_millis(500), extension.get());
+    }
 }
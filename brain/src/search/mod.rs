@@ -8,21 +8,35 @@ use crate::search::transpositions::TranspositionTable;
 use anyhow::{anyhow, Result};
 use myopic_board::Move;
 use orderinghints::OrderingHints;
+use history::SearchHistory;
+use movepicker::MoveListPool;
+use pruning::{LateMoveReductions, NullMovePruning, ReductionTable};
 use serde::ser::SerializeStruct;
 use serde::Serializer;
 use terminator::SearchTerminator;
 use std::marker::PhantomData;
 
+mod history;
 pub mod interactive;
+pub(crate) mod movepicker;
 pub mod negascout;
 mod ordering;
 mod orderinghints;
+pub mod pruning;
 pub mod terminator;
 mod transpositions;
 
 const DEPTH_UPPER_BOUND: usize = 10;
 const SHALLOW_EVAL_TRIGGER_DEPTH: usize = 2;
 const SHALLOW_EVAL_DEPTH: usize = 1;
+/// The first depth searched with an aspiration window
+const ASPIRATION_MIN_DEPTH: usize = 3;
+/// The initial distance of each side of the aspiration window
+/// from the previous eval
+const ASPIRATION_WINDOW: i32 = 50;
+/// A side of the aspiration window which has been widened
+/// beyond this distance is removed entirely
+const ASPIRATION_MAX_WINDOW: i32 = 1_000;
 
 /// API function for executing search on the calling thread, we pass a root
 /// state and a terminator and compute the best move we can make from this
@@ -35,6 +49,8 @@ where
     Search {
         root,
         terminator: parameters.terminator,
+        null_move_pruning: parameters.null_move_pruning,
+        late_move_reductions: parameters.late_move_reductions,
     }
     .search(parameters.table_size)
 }
@@ -42,6 +58,20 @@ where
 pub struct SearchParameters<T: SearchTerminator> {
     pub terminator: T,
     pub table_size: usize,
+    pub null_move_pruning: NullMovePruning,
+    pub late_move_reductions: LateMoveReductions,
+}
+
+impl<T: SearchTerminator> SearchParameters<T> {
+    /// Parameters for a search with the default pruning configuration.
+    pub fn new(terminator: T, table_size: usize) -> SearchParameters<T> {
+        SearchParameters {
+            terminator,
+            table_size,
+            null_move_pruning: NullMovePruning::default(),
+            late_move_reductions: LateMoveReductions::default(),
+        }
+    }
 }
 
 /// Data class composing information/result about/of a best move search.
@@ -54,14 +84,23 @@ pub struct SearchOutcome {
     pub optimal_path: Vec<Move>,
 }
 
+impl SearchOutcome {
+    /// The number of moves until checkmate if one was found, positive if
+    /// the side to move in the root position delivers it.
+    pub fn mate_in(&self) -> Option<i32> {
+        eval::mate_in(self.eval)
+    }
+}
+
 impl serde::Serialize for SearchOutcome {
     fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("SearchOutcome", 4)?;
+        let mut state = serializer.serialize_struct("SearchOutcome", 6)?;
         state.serialize_field("bestMove", &self.best_move.uci_format())?;
         state.serialize_field("positionEval", &self.eval)?;
+        state.serialize_field("mateIn", &self.mate_in())?;
         state.serialize_field("depthSearched", &self.depth)?;
         state.serialize_field("searchDurationMillis", &self.time.as_millis())?;
         state.serialize_field(
@@ -79,7 +118,8 @@ impl serde::Serialize for SearchOutcome {
 #[cfg(test)]
 mod searchoutcome_serialize_test {
     use super::SearchOutcome;
-    use myopic_board::{CastleZone, Move, Piece, Square};
+    use crate::eval;
+    use myopic_board::{CastleZone, Castling, Move, Piece, Square};
     use serde_json;
     use std::time::Duration;
 
@@ -89,6 +129,7 @@ mod searchoutcome_serialize_test {
             best_move: Move::Castle {
                 source: 0,
                 zone: CastleZone::WK,
+                castling: Castling::STANDARD,
             },
             eval: -125,
             depth: 2,
@@ -97,6 +138,7 @@ mod searchoutcome_serialize_test {
                 Move::Castle {
                     source: 0,
                     zone: CastleZone::WK,
+                    castling: Castling::STANDARD,
                 },
                 Move::Standard {
                     source: 1,
@@ -108,7 +150,30 @@ mod searchoutcome_serialize_test {
             ],
         };
         assert_eq!(
-            r#"{"bestMove":"e1g1","positionEval":-125,"depthSearched":2,"searchDurationMillis":3000,"optimalPath":["e1g1","d7d5"]}"#,
+            r#"{"bestMove":"e1g1","positionEval":-125,"mateIn":null,"depthSearched":2,"searchDurationMillis":3000,"optimalPath":["e1g1","d7d5"]}"#,
+            serde_json::to_string(&search_outcome).expect("Serialization failed")
+        );
+    }
+
+    #[test]
+    fn test_json_serialize_mate() {
+        let best_move = Move::Standard {
+            source: 0,
+            moving: Piece::WQ,
+            from: Square::D1,
+            dest: Square::D8,
+            capture: None,
+        };
+        let search_outcome = SearchOutcome {
+            best_move: best_move.clone(),
+            eval: eval::WIN_VALUE - 3,
+            depth: 4,
+            time: Duration::from_millis(500),
+            optimal_path: vec![best_move],
+        };
+        assert_eq!(Some(2), search_outcome.mate_in());
+        assert_eq!(
+            r#"{"bestMove":"d1d8","positionEval":499996,"mateIn":2,"depthSearched":4,"searchDurationMillis":500,"optimalPath":["d1d8"]}"#,
             serde_json::to_string(&search_outcome).expect("Serialization failed")
         );
     }
@@ -117,6 +182,73 @@ mod searchoutcome_serialize_test {
 struct Search<B: EvalChessBoard, T: SearchTerminator> {
     root: B,
     terminator: T,
+    null_move_pruning: NullMovePruning,
+    late_move_reductions: LateMoveReductions,
+}
+
+/// The state which is built up over the iterations of a search.
+struct IterationState<B: EvalChessBoard> {
+    ordering_hints: OrderingHints<B>,
+    transposition_table: TranspositionTable,
+    reductions: ReductionTable,
+    history: SearchHistory,
+    move_lists: MoveListPool,
+}
+
+/// The bounds for searching the root centred on the eval expected from
+/// the previous iteration, each side is widened independently when the
+/// search fails on that side and is removed once it gets too wide.
+struct AspirationWindow {
+    centre: i32,
+    lower: i32,
+    upper: i32,
+}
+
+impl AspirationWindow {
+    /// The window is unbounded for shallow depths or when the previous
+    /// eval is unknown or a mate.
+    fn new(depth: usize, previous_eval: Option<i32>) -> AspirationWindow {
+        match previous_eval {
+            Some(eval) if depth >= ASPIRATION_MIN_DEPTH && !eval::is_mate(eval) => {
+                AspirationWindow {
+                    centre: eval,
+                    lower: ASPIRATION_WINDOW,
+                    upper: ASPIRATION_WINDOW,
+                }
+            }
+            _ => AspirationWindow {
+                centre: 0,
+                lower: eval::INFTY,
+                upper: eval::INFTY,
+            },
+        }
+    }
+
+    fn alpha(&self) -> i32 {
+        if self.lower > ASPIRATION_MAX_WINDOW {
+            -eval::INFTY
+        } else {
+            self.centre - self.lower
+        }
+    }
+
+    fn beta(&self) -> i32 {
+        if self.upper > ASPIRATION_MAX_WINDOW {
+            eval::INFTY
+        } else {
+            self.centre + self.upper
+        }
+    }
+
+    fn fails_low(&self, eval: i32) -> bool {
+        let alpha = self.alpha();
+        eval <= alpha && alpha > -eval::INFTY
+    }
+
+    fn fails_high(&self, eval: i32) -> bool {
+        let beta = self.beta();
+        eval >= beta && beta < eval::INFTY
+    }
 }
 
 struct BestMoveResponse {
@@ -130,24 +262,32 @@ impl<B: EvalChessBoard, T: SearchTerminator> Search<B, T> {
     pub fn search(&self, transposition_table_size: usize) -> Result<SearchOutcome> {
         let search_start = Instant::now();
         let mut break_err = anyhow!("Terminated before search began");
-        let mut ordering_hints = OrderingHints::new(self.root.clone());
-        // TODO inject desired size
-        let mut transposition_table = TranspositionTable::new(transposition_table_size)?;
-        let mut best_response = None;
+        let mut state = IterationState {
+            ordering_hints: OrderingHints::new(self.root.clone()),
+            // TODO inject desired size
+            transposition_table: TranspositionTable::new(transposition_table_size)?,
+            reductions: self.late_move_reductions.table(),
+            history: SearchHistory::default(),
+            move_lists: MoveListPool::default(),
+        };
+        let mut best_response: Option<BestMoveResponse> = None;
 
         for i in 1..DEPTH_UPPER_BOUND {
-            match self.best_move(i, search_start, &ordering_hints, &mut transposition_table) {
+            let previous_eval = best_response.as_ref().map(|r| r.eval);
+            match self.best_move(i, previous_eval, search_start, &mut state) {
                 Err(message) => {
                     break_err = anyhow!("{}", message);
                     break;
                 }
                 Ok(response) => {
-                    ordering_hints.add_pv(i, &response.path);
+                    state.ordering_hints.add_pv(i, &response.path);
                     best_response = Some(response);
                     // Only fill in the shallow eval when we get deep
                     // enough to male it worthwhile
                     if i == SHALLOW_EVAL_TRIGGER_DEPTH {
-                        ordering_hints.populate_shallow_eval(SHALLOW_EVAL_DEPTH);
+                        state
+                            .ordering_hints
+                            .populate_shallow_eval(SHALLOW_EVAL_DEPTH);
                     }
                 }
             }
@@ -164,22 +304,55 @@ impl<B: EvalChessBoard, T: SearchTerminator> Search<B, T> {
             })
     }
 
+    /// Search the root to the given depth. Once deep enough the search
+    /// starts with an aspiration window around the eval of the previous
+    /// iteration, each time the eval falls outside the window the failing
+    /// side is widened until it is unbounded. Fail lows are reported to
+    /// the terminator as they signal the previous best move is in trouble.
     fn best_move(
         &self,
         depth: usize,
+        previous_eval: Option<i32>,
         search_start: Instant,
-        ordering_hints: &OrderingHints<B>,
-        transposition_table: &mut TranspositionTable,
+        state: &mut IterationState<B>,
     ) -> Result<BestMoveResponse> {
         if depth < 1 {
             return Err(anyhow!("Cannot iteratively deepen with depth 0"));
         }
 
-        let SearchResponse { eval, mut path } = Scout {
+        let mut window = AspirationWindow::new(depth, previous_eval);
+        loop {
+            let (alpha, beta) = (window.alpha(), window.beta());
+            let response = self.search_root(depth, alpha, beta, search_start, state)?;
+            if window.fails_low(response.eval) {
+                self.terminator
+                    .on_fail_low(depth, window.centre, response.eval);
+                window.lower *= 2;
+            } else if window.fails_high(response.eval) {
+                window.upper *= 2;
+            } else {
+                return self.best_move_response(depth, response);
+            }
+        }
+    }
+
+    fn search_root(
+        &self,
+        depth: usize,
+        alpha: i32,
+        beta: i32,
+        search_start: Instant,
+        state: &mut IterationState<B>,
+    ) -> Result<SearchResponse> {
+        Scout {
             terminator: &self.terminator,
-            ordering_hints,
+            ordering_hints: &state.ordering_hints,
             move_quality_estimator: EstimatorImpl,
-            transposition_table,
+            transposition_table: &mut state.transposition_table,
+            null_move_pruning: self.null_move_pruning,
+            reductions: &state.reductions,
+            history: &mut state.history,
+            move_lists: &mut state.move_lists,
             board_type: PhantomData,
         }
         .search(
@@ -187,12 +360,21 @@ impl<B: EvalChessBoard, T: SearchTerminator> Search<B, T> {
             SearchContext {
                 depth_remaining: depth,
                 start_time: search_start,
-                alpha: -eval::INFTY,
-                beta: eval::INFTY,
+                alpha,
+                beta,
                 precursors: vec![],
+                null_moves: 0,
+                null_move_allowed: true,
             },
-        )?;
+        )
+    }
 
+    fn best_move_response(
+        &self,
+        depth: usize,
+        response: SearchResponse,
+    ) -> Result<BestMoveResponse> {
+        let SearchResponse { eval, mut path } = response;
         // The path returned from the negamax function is ordered deepest move -> shallowest
         // so we reverse as the shallowest move is the one we make in this position.
         path.reverse();
@@ -219,29 +401,27 @@ impl<B: EvalChessBoard, T: SearchTerminator> Search<B, T> {
 mod test {
     use crate::eval::EvalChessBoard;
     use crate::search::SearchParameters;
-    use crate::{eval, EvalBoard, UciMove};
+    use crate::{EvalBoard, UciMove};
     use myopic_board::{Reflectable, Board};
 
     const DEPTH: usize = 3;
     const TABLE_SIZE: usize = 10_000;
 
-    fn test(fen_string: &'static str, expected_move_pool: Vec<UciMove>, is_won: bool) {
+    fn test(fen_string: &'static str, expected_move_pool: Vec<UciMove>, mate_in: Option<i32>) {
         let base_board = fen_string.parse::<Board>().unwrap();
         let ref_board = EvalBoard::builder(base_board.reflect()).build();
         let board = EvalBoard::builder(base_board).build();
         let ref_move_pool = expected_move_pool.reflect();
-        test_impl(board, expected_move_pool, is_won);
-        test_impl(ref_board, ref_move_pool, is_won);
+        test_impl(board, expected_move_pool, mate_in);
+        test_impl(ref_board, ref_move_pool, mate_in);
     }
 
-    fn test_impl<B: EvalChessBoard>(board: B, expected_move_pool: Vec<UciMove>, is_won: bool) {
-        match super::search(
-            board,
-            SearchParameters {
-                terminator: DEPTH,
-                table_size: TABLE_SIZE,
-            },
-        ) {
+    fn test_impl<B: EvalChessBoard>(
+        board: B,
+        expected_move_pool: Vec<UciMove>,
+        mate_in: Option<i32>,
+    ) {
+        match super::search(board, SearchParameters::new(DEPTH, TABLE_SIZE)) {
             Err(message) => panic!("{}", message),
             Ok(outcome) => {
                 assert!(
@@ -249,9 +429,7 @@ mod test {
                         .contains(&UciMove::new(outcome.best_move.uci_format().as_str()).unwrap()),
                     "{}", serde_json::to_string(&outcome).unwrap()
                 );
-                if is_won {
-                    assert_eq!(eval::WIN_VALUE, outcome.eval);
-                }
+                assert_eq!(mate_in, outcome.mate_in());
             }
         }
     }
@@ -268,7 +446,7 @@ mod test {
                 UciMove::new("a4c2").unwrap(),
                 UciMove::new("a4d1").unwrap(),
             ],
-            false,
+            None,
         )
     }
 
@@ -277,7 +455,7 @@ mod test {
         test(
             "r2r2k1/5ppp/1N2p3/1n6/3Q4/2B5/5PPP/1R3RK1 w Qq - 4 21",
             vec![UciMove::new("d4g7").unwrap()],
-            true,
+            Some(1),
         )
     }
 
@@ -286,7 +464,7 @@ mod test {
         test(
             "8/8/8/4Q3/8/6R1/2n1pkBK/8 w - - 0 1",
             vec![UciMove::new("g3d3").unwrap()],
-            true,
+            Some(2),
         )
     }
 
@@ -295,7 +473,7 @@ mod test {
         test(
             "8/7B/5Q2/6p1/6k1/8/5K2/8 w - - 0 1",
             vec![UciMove::new("f6h8").unwrap(), UciMove::new("f6f3").unwrap()],
-            true,
+            Some(2),
         )
     }
 
@@ -304,7 +482,7 @@ mod test {
         test(
             "3qr2k/1b1p2pp/7N/3Q2b1/4P3/8/5PP1/6K1 w - - 0 1",
             vec![UciMove::new("d5g8").unwrap()],
-            true,
+            Some(2),
         )
     }
 
@@ -315,7 +493,7 @@ mod test {
         test(
             "r1k2b1r/pp4pp/2p1n3/3NQ1B1/6q1/8/PPP2P1P/2KR4 w - - 4 20",
             vec![UciMove::new("e5c7").unwrap()],
-            true,
+            Some(4),
         )
     }
 
@@ -324,7 +502,7 @@ mod test {
         test(
             "r1b1k1nr/p2p1ppp/n2B4/1p1NPN1P/6P1/3P1Q2/P1P1K3/q5b1 w - - 0 30",
             vec![UciMove::new("f5g7").unwrap()],
-            true,
+            Some(3),
         )
     }
 
@@ -336,7 +514,7 @@ mod test {
         test(
             "1r3k2/2R5/1p2p2p/1Q1pPp1q/1P1P2p1/2P1P1P1/6KP/8 b - - 2 31",
             vec![UciMove::new("b8a8").unwrap()],
-            false,
+            None,
         )
     }
 
@@ -348,7 +526,101 @@ mod test {
         test(
             "r5k1/pb4pp/1pn1pq2/5B2/2Pr4/B7/PP3RPP/R4QK1 b - - 0 23",
             vec![UciMove::new("e6f5").unwrap()],
-            false,
+            None,
         )
     }
 }
+
+#[cfg(test)]
+mod aspiration_test {
+    use super::{AspirationWindow, ASPIRATION_MAX_WINDOW, ASPIRATION_MIN_DEPTH, ASPIRATION_WINDOW};
+    use crate::eval;
+    use crate::search::negascout::SearchContext;
+    use crate::search::terminator::SearchTerminator;
+    use crate::search::SearchParameters;
+    use crate::EvalBoard;
+    use anyhow::Result;
+    use itertools::Itertools;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Terminates at a fixed depth and records every fail low
+    struct FailLowRecorder {
+        depth: usize,
+        fail_lows: Rc<RefCell<Vec<(usize, i32, i32)>>>,
+    }
+
+    impl SearchTerminator for FailLowRecorder {
+        fn should_terminate(&self, ctx: &SearchContext) -> bool {
+            self.depth.should_terminate(ctx)
+        }
+
+        fn on_fail_low(&self, depth: usize, previous_eval: i32, upper_bound: i32) {
+            self.fail_lows
+                .borrow_mut()
+                .push((depth, previous_eval, upper_bound));
+        }
+    }
+
+    #[test]
+    fn fail_lows_are_reported_and_window_widens() -> Result<()> {
+        let fen = "rnbqr1k1/pp3pbp/3ppnp1/2pP4/2P1PP2/2N2N2/PPQ1B1PP/R1B1K2R b KQ - 3 9";
+        let fail_lows = Rc::new(RefCell::new(vec![]));
+        let outcome = super::search(
+            EvalBoard::builder_fen(fen)?.build(),
+            SearchParameters::new(
+                FailLowRecorder {
+                    depth: 4,
+                    fail_lows: fail_lows.clone(),
+                },
+                10_000,
+            ),
+        )?;
+        assert_eq!(4, outcome.depth);
+        // The window is reset at every depth and doubles with each fail low
+        let fail_lows = fail_lows.borrow();
+        assert!(fail_lows.len() >= 2, "{:?}", fail_lows);
+        for (depth, events) in &fail_lows.iter().group_by(|(depth, _, _)| *depth) {
+            assert!(depth >= ASPIRATION_MIN_DEPTH);
+            let mut window = ASPIRATION_WINDOW;
+            for &(_, previous_eval, upper_bound) in events {
+                assert_eq!(previous_eval - window, upper_bound);
+                window *= 2;
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn window_unbounded_when_shallow_or_mating() {
+        for window in &[
+            AspirationWindow::new(ASPIRATION_MIN_DEPTH - 1, Some(100)),
+            AspirationWindow::new(ASPIRATION_MIN_DEPTH, None),
+            AspirationWindow::new(ASPIRATION_MIN_DEPTH, Some(eval::WIN_VALUE - 3)),
+        ] {
+            assert_eq!((-eval::INFTY, eval::INFTY), (window.alpha(), window.beta()));
+            assert!(!window.fails_low(-eval::WIN_VALUE));
+            assert!(!window.fails_high(eval::WIN_VALUE));
+        }
+    }
+
+    #[test]
+    fn fail_high_widens_upper_side_only() {
+        let mut window = AspirationWindow::new(ASPIRATION_MIN_DEPTH, Some(100));
+        let (alpha, beta) = (100 - ASPIRATION_WINDOW, 100 + ASPIRATION_WINDOW);
+        assert_eq!((alpha, beta), (window.alpha(), window.beta()));
+        assert!(window.fails_high(100 + ASPIRATION_WINDOW));
+        assert!(!window.fails_high(100 + ASPIRATION_WINDOW - 1));
+        let mut beta = window.beta();
+        while window.fails_high(eval::WIN_VALUE) {
+            window.upper *= 2;
+            assert!(window.beta() > beta);
+            assert_eq!(alpha, window.alpha());
+            beta = window.beta();
+        }
+        // Once widened past the maximum the upper side is removed
+        assert_eq!(eval::INFTY, window.beta());
+        assert!(window.upper > ASPIRATION_MAX_WINDOW);
+        assert!(window.fails_low(alpha));
+    }
+}
@@ -0,0 +1,380 @@
+use crate::search::history::HistoryTable;
+use crate::search::ordering::MoveQualityEstimator;
+use crate::{see, EvalChessBoard};
+use myopic_board::{ChessBoard, Move, MoveComputeType, MoveList, MAX_MOVES};
+
+/// A fixed capacity list of moves each paired with a score which can be
+/// retrieved lazily in descending score order. Each retrieval performs a
+/// single selection pass which is cheaper than sorting the whole list
+/// when a cutoff means only the first few moves are ever searched.
+pub struct ScoredMoves {
+    moves: MoveList,
+    scores: [i32; MAX_MOVES],
+}
+
+impl Default for ScoredMoves {
+    fn default() -> Self {
+        ScoredMoves {
+            moves: MoveList::default(),
+            scores: [0; MAX_MOVES],
+        }
+    }
+}
+
+impl ScoredMoves {
+    /// Replace the contents of this list with the moves of the given type
+    /// in the given position, scoring each with the given function.
+    pub fn fill<B, F>(&mut self, board: &mut B, computation_type: MoveComputeType, mut score: F)
+    where
+        B: ChessBoard,
+        F: FnMut(&mut B, &Move) -> i32,
+    {
+        board.compute_moves_into(computation_type, &mut self.moves);
+        for i in 0..self.moves.len() {
+            self.scores[i] = score(board, &self.moves[i]);
+        }
+    }
+
+    pub fn push(&mut self, mv: Move, score: i32) {
+        self.scores[self.moves.len()] = score;
+        self.moves.push(mv);
+    }
+
+    /// Remove and return the move with the highest score.
+    pub fn pop_best(&mut self) -> Option<(Move, i32)> {
+        let len = self.moves.len();
+        let best = (0..len).max_by_key(|&i| self.scores[i])?;
+        Some(self.remove(best))
+    }
+
+    /// Remove every move which satisfies the given predicate, passing each
+    /// to the destination list if one is given.
+    pub fn remove_where<F>(&mut self, predicate: F, mut dest: Option<&mut ScoredMoves>)
+    where
+        F: Fn(&Move, i32) -> bool,
+    {
+        for i in (0..self.moves.len()).rev() {
+            if predicate(&self.moves[i], self.scores[i]) {
+                let (mv, score) = self.remove(i);
+                if let Some(dest) = dest.as_mut() {
+                    dest.push(mv, score);
+                }
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.moves.clear();
+    }
+
+    fn position(&self, predicate: impl Fn(&Move) -> bool) -> Option<usize> {
+        self.moves.iter().position(predicate)
+    }
+
+    fn remove(&mut self, index: usize) -> (Move, i32) {
+        let score = self.scores[index];
+        self.scores[index] = self.scores[self.moves.len() - 1];
+        (self.moves.swap_remove(index), score)
+    }
+}
+
+/// Scored move lists which are no longer in use by the node they were
+/// taken for. Each list is large so they are boxed and recycled between
+/// the nodes of a search instead of being zero initialised on the stack
+/// of every node, a new list is only allocated when none are free.
+#[derive(Default)]
+pub struct MoveListPool {
+    free: Vec<Box<ScoredMoves>>,
+}
+
+impl MoveListPool {
+    /// Take an empty list from the pool.
+    pub fn take(&mut self) -> Box<ScoredMoves> {
+        self.free.pop().unwrap_or_default()
+    }
+
+    /// Return a list to the pool once it is no longer needed.
+    pub fn recycle(&mut self, mut list: Box<ScoredMoves>) {
+        list.clear();
+        self.free.push(list);
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Stage {
+    Ordered,
+    TableMove,
+    GenerateCaptures,
+    GoodCaptures,
+    GenerateQuiets,
+    Refutations(usize),
+    Quiets,
+    BadCaptures,
+}
+
+/// Lazily yields the legal moves in a position for the search in stages,
+/// each stage is only generated once the previous one is exhausted so a
+/// cutoff early on means the later stages are never computed. The stages
+/// in order are:
+///
+/// 1. The move suggested by the transposition table
+/// 2. Captures and promotions with a positive exchange value
+/// 3. Killer moves then the countermove to the previous move, each only
+///    if it is a legal quiet move in this position
+/// 4. The remaining quiet moves ordered by the move quality estimator
+/// 5. The remaining captures ordered by exchange value
+pub struct MovePicker {
+    stage: Stage,
+    ordered: Vec<Move>,
+    table_move: Option<Move>,
+    refutations: [Option<Move>; 3],
+    current: Box<ScoredMoves>,
+    bad_captures: Box<ScoredMoves>,
+}
+
+impl MovePicker {
+    /// Create a staged picker. The table move is only yielded if it is
+    /// legal in the position the picker is used for, the killers and the
+    /// countermove need only match the piece, origin and destination of
+    /// a legal quiet move. The move lists are taken from the given pool.
+    pub fn new(
+        table_move: Option<Move>,
+        killers: [Option<Move>; 2],
+        countermove: Option<Move>,
+        pool: &mut MoveListPool,
+    ) -> MovePicker {
+        let [first, second] = killers;
+        MovePicker {
+            stage: Stage::TableMove,
+            ordered: vec![],
+            table_move,
+            refutations: [first, second, countermove],
+            current: pool.take(),
+            bad_captures: pool.take(),
+        }
+    }
+
+    /// Create a picker which simply yields the given moves in order.
+    pub fn ordered(mut moves: Vec<Move>, pool: &mut MoveListPool) -> MovePicker {
+        moves.reverse();
+        MovePicker {
+            stage: Stage::Ordered,
+            ordered: moves,
+            table_move: None,
+            refutations: [None, None, None],
+            current: pool.take(),
+            bad_captures: pool.take(),
+        }
+    }
+
+    /// Return the move lists of this picker to the pool they were taken
+    /// from so they can be reused by another node.
+    pub fn recycle(self, pool: &mut MoveListPool) {
+        pool.recycle(self.current);
+        pool.recycle(self.bad_captures);
+    }
+
+    /// Return the next move to search in the given position which must be
+    /// the same position at every call.
+    pub fn next<B, M>(
+        &mut self,
+        board: &mut B,
+        estimator: &M,
+        history: &HistoryTable,
+    ) -> Option<Move>
+    where
+        B: EvalChessBoard,
+        M: MoveQualityEstimator<B>,
+    {
+        loop {
+            match self.stage {
+                Stage::Ordered => return self.ordered.pop(),
+                Stage::TableMove => {
+                    self.stage = Stage::GenerateCaptures;
+                    match &self.table_move {
+                        Some(mv) if board.is_legal(mv) => return Some(mv.clone()),
+                        _ => self.table_move = None,
+                    }
+                }
+                Stage::GenerateCaptures => {
+                    self.current
+                        .fill(board, MoveComputeType::Captures, score_capture);
+                    let table_move = &self.table_move;
+                    self.current
+                        .remove_where(|m, _| Some(m) == table_move.as_ref(), None);
+                    self.current
+                        .remove_where(|_, score| score <= 0, Some(&mut self.bad_captures));
+                    self.stage = Stage::GoodCaptures;
+                }
+                Stage::GoodCaptures => match self.current.pop_best() {
+                    Some((mv, _)) => return Some(mv),
+                    None => self.stage = Stage::GenerateQuiets,
+                },
+                Stage::GenerateQuiets => {
+                    self.current.fill(board, MoveComputeType::Quiets, |b, m| {
+                        estimator.estimate(b, m, history)
+                    });
+                    let table_move = &self.table_move;
+                    self.current
+                        .remove_where(|m, _| Some(m) == table_move.as_ref(), None);
+                    self.stage = Stage::Refutations(0);
+                }
+                Stage::Refutations(i) => {
+                    if i == self.refutations.len() {
+                        self.stage = Stage::Quiets;
+                        continue;
+                    }
+                    self.stage = Stage::Refutations(i + 1);
+                    if let Some(refutation) = &self.refutations[i] {
+                        let matches = |m: &Move| is_same_action(m, refutation);
+                        if let Some(index) = self.current.position(matches) {
+                            return Some(self.current.remove(index).0);
+                        }
+                    }
+                }
+                Stage::Quiets => match self.current.pop_best() {
+                    Some((mv, _)) => return Some(mv),
+                    None => self.stage = Stage::BadCaptures,
+                },
+                Stage::BadCaptures => return self.bad_captures.pop_best().map(|(mv, _)| mv),
+            }
+        }
+    }
+}
+
+/// Score a capture or promotion by the material it is expected to win.
+pub fn score_capture<B: EvalChessBoard>(board: &mut B, mv: &Move) -> i32 {
+    match *mv {
+        Move::Enpassant { .. } => 10000,
+        Move::Promotion { promoted, .. } => 20000 + board.piece_values()[promoted as usize % 6],
+        Move::Standard { from, dest, .. } => {
+            see::exchange_value(board, from, dest, board.piece_values())
+        }
+        // Should never get here
+        _ => 0,
+    }
+}
+
+/// Check if the two moves perform the same action ignoring the position
+/// they were created in, only standard and castling moves can match.
+fn is_same_action(left: &Move, right: &Move) -> bool {
+    match (left, right) {
+        (
+            Move::Standard {
+                moving: lm,
+                from: lf,
+                dest: ld,
+                capture: lc,
+                ..
+            },
+            Move::Standard {
+                moving: rm,
+                from: rf,
+                dest: rd,
+                capture: rc,
+                ..
+            },
+        ) => (lm, lf, ld, lc) == (rm, rf, rd, rc),
+        (Move::Castle { zone: lz, .. }, Move::Castle { zone: rz, .. }) => lz == rz,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::search::ordering::EstimatorImpl;
+    use crate::EvalBoard;
+    use anyhow::Result;
+    use std::collections::BTreeSet;
+
+    fn pick_all(board: &mut EvalBoard<myopic_board::Board>, picker: &mut MovePicker) -> Vec<Move> {
+        let mut dest = vec![];
+        while let Some(mv) = picker.next(board, &EstimatorImpl, &HistoryTable::default()) {
+            dest.push(mv);
+        }
+        dest
+    }
+
+    #[test]
+    fn yields_every_legal_move_once() -> Result<()> {
+        let mut board = EvalBoard::builder_fen(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        )?
+        .build();
+        let legal = board.compute_moves(MoveComputeType::All);
+        let table_move = board.parse_uci("e1g1")?;
+        let killer = board.parse_uci("a2a3")?;
+        let countermove = board.parse_uci("b2b3")?;
+        let mut picker = MovePicker::new(
+            Some(table_move.clone()),
+            [Some(killer.clone()), None],
+            Some(countermove.clone()),
+            &mut MoveListPool::default(),
+        );
+        let picked = pick_all(&mut board, &mut picker);
+        assert_eq!(legal.len(), picked.len());
+        assert_eq!(
+            legal.into_iter().collect::<BTreeSet<_>>(),
+            picked.iter().cloned().collect::<BTreeSet<_>>()
+        );
+        let position = |mv: &Move| picked.iter().position(|m| m == mv).unwrap();
+        assert_eq!(0, position(&table_move));
+        // Winning captures come before the killer then the quiet moves
+        let good_capture = board.parse_uci("e2a6")?;
+        let quiet = board.parse_uci("g2g3")?;
+        let bad_capture = board.parse_uci("f3f6")?;
+        assert!(position(&good_capture) < position(&killer));
+        assert!(position(&killer) < position(&countermove));
+        assert!(position(&countermove) < position(&quiet));
+        assert!(position(&quiet) < position(&bad_capture));
+        Ok(())
+    }
+
+    #[test]
+    fn killer_from_other_position_matches_on_action() -> Result<()> {
+        let mut board = EvalBoard::start();
+        let killer = Move::Standard {
+            source: 0,
+            moving: myopic_board::Piece::WN,
+            from: myopic_board::Square::G1,
+            dest: myopic_board::Square::F3,
+            capture: None,
+        };
+        let mut pool = MoveListPool::default();
+        let mut picker = MovePicker::new(None, [None, Some(killer)], None, &mut pool);
+        let picked = pick_all(&mut board, &mut picker);
+        assert_eq!(20, picked.len());
+        assert_eq!(board.parse_uci("g1f3")?, picked[0]);
+        Ok(())
+    }
+
+    #[test]
+    fn recycled_lists_are_reused_empty() -> Result<()> {
+        let mut board = EvalBoard::start();
+        let mut pool = MoveListPool::default();
+        let mut picker = MovePicker::new(None, [None, None], None, &mut pool);
+        // Stop part way through as if there was a cutoff
+        for _ in 0..5 {
+            picker.next(&mut board, &EstimatorImpl, &HistoryTable::default());
+        }
+        picker.recycle(&mut pool);
+        assert_eq!(2, pool.free.len());
+        let mut picker = MovePicker::new(None, [None, None], None, &mut pool);
+        assert!(pool.free.is_empty());
+        assert_eq!(20, pick_all(&mut board, &mut picker).len());
+        Ok(())
+    }
+
+    #[test]
+    fn table_move_from_other_position_is_ignored() -> Result<()> {
+        let mut board = EvalBoard::start();
+        let mut other = EvalBoard::start();
+        other.play_uci("e2e4")?;
+        let table_move = other.parse_uci("e7e5")?;
+        let mut pool = MoveListPool::default();
+        let mut picker = MovePicker::new(Some(table_move), [None, None], None, &mut pool);
+        assert_eq!(20, pick_all(&mut board, &mut picker).len());
+        Ok(())
+    }
+}
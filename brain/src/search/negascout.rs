@@ -1,13 +1,16 @@
 use crate::search::eval;
-use crate::search::ordering::{EstimatorImpl, MoveQualityEstimator};
+use crate::search::history::{is_quiet, SearchHistory};
+use crate::search::movepicker::{MoveListPool, MovePicker};
+use crate::search::ordering::{get_category, EstimatorImpl, MoveQualityEstimator};
 use crate::search::orderinghints::OrderingHints;
+use crate::search::pruning::{LateMoveReductions, NullMovePruning, ReductionTable};
 use crate::search::terminator::SearchTerminator;
 use crate::search::transpositions::{TranspositionTable, TreeNode};
 use crate::{quiescent, EvalChessBoard};
 use anyhow::{anyhow, Result};
 use core::cmp;
 use itertools::Itertools;
-use myopic_board::{Move, MoveComputeType, Termination};
+use myopic_board::{CompactMove, Move, MoveComputeType, Piece, Termination};
 use std::time::Instant;
 use std::marker::PhantomData;
 
@@ -25,6 +28,10 @@ where
         ordering_hints: &OrderingHints::new(root.clone()),
         transposition_table: &mut TranspositionTable::new(1)?,
         move_quality_estimator: EstimatorImpl,
+        null_move_pruning: NullMovePruning::default(),
+        reductions: &LateMoveReductions::default().table(),
+        history: &mut SearchHistory::default(),
+        move_lists: &mut MoveListPool::default(),
         board_type: PhantomData,
     }
     .search(
@@ -35,6 +42,8 @@ where
             beta: eval::INFTY,
             depth_remaining: depth,
             precursors: vec![],
+            null_moves: 0,
+            null_move_allowed: true,
         },
     )
 }
@@ -46,19 +55,80 @@ pub struct SearchContext {
     pub alpha: i32,
     pub beta: i32,
     pub depth_remaining: usize,
-    pub precursors: Vec<Move>,
+    pub precursors: Vec<CompactMove>,
+    /// The number of null moves made on the path from the root, these
+    /// are not recorded in the precursors.
+    pub null_moves: usize,
+    /// Whether a null move may be tried in this node, it is not allowed
+    /// directly after another null move or while verifying a cutoff.
+    pub null_move_allowed: bool,
 }
 
 impl SearchContext {
+    /// The number of half moves made from the root of the search.
+    pub fn ply(&self) -> usize {
+        self.precursors.len() + self.null_moves
+    }
+
+    /// The move made to reach this node, unknown if there is a null move
+    /// anywhere on the path from the root.
+    pub fn previous_move(&self) -> Option<CompactMove> {
+        if self.null_moves == 0 {
+            self.precursors.last().cloned()
+        } else {
+            None
+        }
+    }
+
     fn next_level(&self, next_alpha: i32, next_beta: i32, mv: &Move) -> SearchContext {
+        self.reduced_level(next_alpha, next_beta, mv, 0)
+    }
+
+    fn reduced_level(
+        &self,
+        next_alpha: i32,
+        next_beta: i32,
+        mv: &Move,
+        reduction: usize,
+    ) -> SearchContext {
         let mut next_precursors = self.precursors.clone();
-        next_precursors.push(mv.clone());
+        next_precursors.push(mv.compact());
         SearchContext {
             start_time: self.start_time,
             alpha: next_alpha,
             beta: next_beta,
-            depth_remaining: self.depth_remaining - 1,
+            depth_remaining: self.depth_remaining.saturating_sub(reduction + 1),
             precursors: next_precursors,
+            null_moves: self.null_moves,
+            null_move_allowed: true,
+        }
+    }
+
+    /// The context for the opponent to search the position after a null
+    /// move with a zero window around our beta.
+    fn null_level(&self, reduction: usize) -> SearchContext {
+        SearchContext {
+            start_time: self.start_time,
+            alpha: -self.beta,
+            beta: -self.beta + 1,
+            depth_remaining: self.depth_remaining.saturating_sub(reduction + 1),
+            precursors: self.precursors.clone(),
+            null_moves: self.null_moves + 1,
+            null_move_allowed: false,
+        }
+    }
+
+    /// The context for re-searching this node at reduced depth without
+    /// a null move to confirm a null move cutoff.
+    fn verification_level(&self, reduction: usize) -> SearchContext {
+        SearchContext {
+            start_time: self.start_time,
+            alpha: self.beta - 1,
+            beta: self.beta,
+            depth_remaining: self.depth_remaining.saturating_sub(reduction),
+            precursors: self.precursors.clone(),
+            null_moves: self.null_moves,
+            null_move_allowed: false,
         }
     }
 }
@@ -117,6 +187,17 @@ where
     /// Used for performing an initial sort on the moves
     /// generated in each position for optimising the search
     pub move_quality_estimator: M,
+    /// Configuration for pruning non pv nodes where passing
+    /// the turn still fails high
+    pub null_move_pruning: NullMovePruning,
+    /// Depth reductions for moves ordered late in a node
+    pub reductions: &'a ReductionTable,
+    /// Killers, countermoves and scores for quiet moves
+    /// according to how often they have caused a cutoff,
+    /// shared across an iterative deepening run
+    pub history: &'a mut SearchHistory,
+    /// Move lists recycled between the nodes of the search
+    pub move_lists: &'a mut MoveListPool,
     /// Placeholder to satisfy the compiler because of the 'unused'
     /// type parameter for the board
     pub board_type: std::marker::PhantomData<B>,
@@ -150,13 +231,14 @@ where
             Err(anyhow!("Terminated at depth {}", ctx.depth_remaining))
         } else if ctx.depth_remaining == 0 || root.termination_status().is_some() {
             match root.termination_status() {
-                Some(Termination::Loss) => Ok(eval::LOSS_VALUE),
-                Some(Termination::Draw) => Ok(eval::DRAW_VALUE),
-                None => quiescent::search(root, -eval::INFTY, eval::INFTY, -1),
+                Some(Termination::Loss) => Ok(eval::loss_in(ctx.ply())),
+                Some(Termination::Draw(_)) => Ok(eval::DRAW_VALUE),
+                None => quiescent::search(root, -eval::INFTY, eval::INFTY, -1, self.move_lists)
+                    .map(|eval| eval::to_root_relative(eval, ctx.ply())),
             }
             .map(|eval| SearchResponse { eval, path: vec![] })
         } else {
-            let (hash, mut table_suggestion) = (root.hash(), None);
+            let (hash, ply, mut table_suggestion) = (root.hash(), ctx.ply(), None);
             match self.transposition_table.get(hash) {
                 None => {}
                 Some(TreeNode::Pv {
@@ -164,18 +246,24 @@ where
                     eval,
                     optimal_path,
                 }) => {
-                    if (*depth as usize) >= ctx.depth_remaining {
+                    let expanded = if (*depth as usize) >= ctx.depth_remaining {
+                        expand_path(root, optimal_path).ok()
+                    } else {
+                        None
+                    };
+                    if let Some(path) = expanded {
                         // We already searched this position fully at a sufficient depth
                         return Ok(SearchResponse {
-                            eval: *eval,
-                            path: optimal_path.clone(),
+                            eval: eval::to_root_relative(*eval, ply),
+                            path,
                         });
                     } else {
                         // The depth wasn't sufficient and so we only have a suggestion
                         // for the best move
                         table_suggestion = optimal_path
                             .last()
-                            .map(|m| TableSuggestion::Pv(*depth, m.clone()))
+                            .and_then(|m| m.expand(root).ok())
+                            .map(|m| TableSuggestion::Pv(*depth, m))
                     }
                 }
                 Some(TreeNode::Cut {
@@ -183,13 +271,14 @@ where
                     beta,
                     cutoff_move,
                 }) => {
-                    if (*depth as usize) >= ctx.depth_remaining && ctx.beta <= *beta {
+                    let beta = eval::to_root_relative(*beta, ply);
+                    if (*depth as usize) >= ctx.depth_remaining && ctx.beta <= beta {
                         return Ok(SearchResponse {
                             eval: ctx.beta,
                             path: vec![],
                         });
                     } else {
-                        table_suggestion = Some(TableSuggestion::Cut(cutoff_move.clone()));
+                        table_suggestion = cutoff_move.expand(root).ok().map(TableSuggestion::Cut);
                     }
                 }
                 Some(TreeNode::All {
@@ -197,23 +286,42 @@ where
                     eval,
                     best_move,
                 }) => {
-                    if (*depth as usize) >= ctx.depth_remaining && *eval <= ctx.alpha {
+                    let eval = eval::to_root_relative(*eval, ply);
+                    if (*depth as usize) >= ctx.depth_remaining && eval <= ctx.alpha {
+                        return Ok(SearchResponse { eval, path: vec![] });
+                    } else {
+                        table_suggestion = best_move.expand(root).ok().map(TableSuggestion::All);
+                    }
+                }
+            };
+
+            if self.should_try_null_move(root, &ctx) {
+                let reduction = self.null_move_pruning.reduction(ctx.depth_remaining);
+                root.make_null()?;
+                let null_eval = -self.search(root, ctx.null_level(reduction))?.eval;
+                root.unmake_null()?;
+                // Mate scores found after passing the turn are not sound
+                // so they never justify a cutoff
+                if null_eval >= ctx.beta && !eval::is_mate(null_eval) {
+                    let verified = ctx.depth_remaining < self.null_move_pruning.verification_depth
+                        || self.search(root, ctx.verification_level(reduction))?.eval >= ctx.beta;
+                    if verified {
                         return Ok(SearchResponse {
-                            eval: *eval,
+                            eval: ctx.beta,
                             path: vec![],
                         });
-                    } else {
-                        table_suggestion = Some(TableSuggestion::All(best_move.clone()));
                     }
                 }
-            };
+            }
 
             let (start_alpha, mut result, mut best_path) = (ctx.alpha, -eval::INFTY, vec![]);
-            for (i, evolve) in self
-                .compute_moves(root, &ctx.precursors, table_suggestion)
-                .into_iter()
-                .enumerate()
+            let mut moves = self.move_picker(root, &ctx, table_suggestion)?;
+            let (in_check, mut searched_quiets) = (root.in_check(), vec![]);
+            let mut i = 0;
+            while let Some(evolve) =
+                moves.next(root, &self.move_quality_estimator, &self.history.butterfly)
             {
+                let reduction = self.late_move_reduction(root, &ctx, &evolve, i, in_check);
                 root.make(evolve.clone())?;
                 #[allow(unused_assignments)]
                 let mut response = SearchResponse::default();
@@ -224,9 +332,18 @@ where
                         -self.search(root, ctx.next_level(-ctx.beta, -ctx.alpha, &evolve))?;
                 } else {
                     // Search with null window under the assumption that the
-                    // previous moves are better than this
-                    response =
-                        -self.search(root, ctx.next_level(-ctx.alpha - 1, -ctx.alpha, &evolve))?;
+                    // previous moves are better than this, late moves are
+                    // also searched to a reduced depth
+                    response = -self.search(
+                        root,
+                        ctx.reduced_level(-ctx.alpha - 1, -ctx.alpha, &evolve, reduction),
+                    )?;
+                    // A reduced search which raises alpha is not trusted
+                    // until it has been repeated at full depth
+                    if reduction > 0 && response.eval > ctx.alpha {
+                        response = -self
+                            .search(root, ctx.next_level(-ctx.alpha - 1, -ctx.alpha, &evolve))?;
+                    }
                     // If there is some move which can raise alpha
                     if ctx.alpha < response.eval && response.eval < ctx.beta {
                         // Then this was actually a better move and so we must
@@ -236,6 +353,7 @@ where
                     }
                 }
                 root.unmake()?;
+                i += 1;
 
                 if response.eval > result {
                     result = response.eval;
@@ -246,20 +364,34 @@ where
                 ctx.alpha = cmp::max(ctx.alpha, result);
                 if ctx.alpha >= ctx.beta {
                     // We are a cut node
+                    if is_quiet(&evolve) {
+                        self.history.record_cutoff(
+                            ply,
+                            ctx.depth_remaining,
+                            ctx.previous_move(),
+                            &evolve,
+                            &searched_quiets,
+                        );
+                    }
                     self.transposition_table.insert(
                         hash,
                         TreeNode::Cut {
                             depth: ctx.depth_remaining as u8,
-                            beta: ctx.beta,
-                            cutoff_move: evolve,
+                            beta: eval::to_node_relative(ctx.beta, ply),
+                            cutoff_move: evolve.compact(),
                         },
                     );
+                    moves.recycle(self.move_lists);
                     return Ok(SearchResponse {
                         eval: ctx.beta,
                         path: vec![],
                     });
                 }
+                if is_quiet(&evolve) {
+                    searched_quiets.push(evolve);
+                }
             }
+            moves.recycle(self.move_lists);
 
             // Populate the table with the information from this node.
             if ctx.alpha == start_alpha {
@@ -272,8 +404,8 @@ where
                         hash,
                         TreeNode::All {
                             depth: ctx.depth_remaining as u8,
-                            eval: result,
-                            best_move: mv.clone(),
+                            eval: eval::to_node_relative(result, ply),
+                            best_move: mv.compact(),
                         },
                     ),
                 }
@@ -283,8 +415,8 @@ where
                     hash,
                     TreeNode::Pv {
                         depth: ctx.depth_remaining as u8,
-                        eval: result,
-                        optimal_path: best_path.clone(),
+                        eval: eval::to_node_relative(result, ply),
+                        optimal_path: best_path.iter().map(|m| m.compact()).collect(),
                     },
                 )
             }
@@ -296,47 +428,128 @@ where
         }
     }
 
+    /// Null moves are only tried in zero window nodes which are not
+    /// expected to be on the principal variation. The side to move must
+    /// not be in check and must have a piece other than pawns, otherwise
+    /// zugzwang makes passing the turn an unreliable lower bound.
+    fn should_try_null_move(&self, root: &mut B, ctx: &SearchContext) -> bool {
+        let params = &self.null_move_pruning;
+        if !params.enabled
+            || !ctx.null_move_allowed
+            || ctx.beta - ctx.alpha != 1
+            || ctx.depth_remaining < params.min_depth
+            || eval::is_mate(ctx.beta)
+        {
+            return false;
+        }
+        let active = root.active();
+        let pawns_and_king = root.locs(&[Piece::pawn(active), Piece::king(active)]);
+        (root.side(active) - pawns_and_king).is_populated()
+            && !root.in_check()
+            && root.static_eval() >= ctx.beta
+    }
+
+    /// Late moves are searched at a reduced depth according to their
+    /// index, category and history unless the side to move is in check
+    /// or the move gives check.
+    fn late_move_reduction(
+        &self,
+        root: &mut B,
+        ctx: &SearchContext,
+        mv: &Move,
+        index: usize,
+        in_check: bool,
+    ) -> usize {
+        if in_check || !self.reductions.applies(ctx.depth_remaining, index) || root.gives_check(mv)
+        {
+            return 0;
+        }
+        let category = get_category(root, mv);
+        let (history, pv_node) = (self.history.butterfly.score(mv), ctx.beta - ctx.alpha > 1);
+        self.reductions
+            .reduction(ctx.depth_remaining, index, &category, history, pv_node)
+    }
+
     fn compute_heuristically_ordered_moves(&self, board: &mut B) -> Vec<Move> {
         let mut moves = board.compute_moves(MoveComputeType::All);
-        moves.sort_by_cached_key(|m| -self.move_quality_estimator.estimate(board, m));
+        let (estimator, history) = (&self.move_quality_estimator, &self.history.butterfly);
+        moves.sort_by_cached_key(|m| -estimator.estimate(board, m, history));
         moves
     }
 
-    fn compute_moves(
-        &self,
+    /// Without any precomputed hints for this position the moves are
+    /// generated lazily in stages using the killers and countermove from
+    /// the search history, otherwise the hints determine the full ordering
+    /// up front.
+    fn move_picker(
+        &mut self,
         board: &mut B,
-        precursors: &Vec<Move>,
+        ctx: &SearchContext,
         table_suggestion: Option<TableSuggestion>,
-    ) -> Vec<Move> {
-        let sm = self.ordering_hints;
-        match (sm.get_pvs(precursors), sm.get_evs(precursors)) {
+    ) -> Result<MovePicker> {
+        let (sm, precursors) = (self.ordering_hints, &ctx.precursors);
+        // The hints are keyed by the moves played from the root so they
+        // don't apply to positions reached through a null move
+        let hints = if ctx.null_moves == 0 {
+            (sm.get_pvs(precursors), sm.get_evs(precursors))
+        } else {
+            (None, None)
+        };
+        let (pvs, evs) = match hints {
             (None, None) => {
-                let mut mvs = self.compute_heuristically_ordered_moves(board);
-                check_and_reposition_first(&mut mvs, table_suggestion);
-                mvs
-            }
-
-            (Some(pvs_ref), None) => {
-                let pvs = pvs_ref.iter().map(|m| m.mv.clone()).collect_vec();
-                let mut all_mvs = self.compute_heuristically_ordered_moves(board);
-                check_and_reposition_first(&mut all_mvs, table_suggestion);
-                pvs.into_iter().chain(all_mvs.into_iter()).dedup().collect()
+                let table_move = table_suggestion.map(|ts| ts.mv());
+                let killers = self.history.killers.get(ctx.ply());
+                let countermove = ctx
+                    .previous_move()
+                    .and_then(|mv| self.history.countermoves.get(mv));
+                return Ok(MovePicker::new(
+                    table_move,
+                    killers,
+                    countermove,
+                    self.move_lists,
+                ));
             }
+            (pvs, evs) => (
+                expand_all(board, pvs.into_iter().flatten().map(|m| m.mv))?,
+                expand_all(board, evs.into_iter().flatten().map(|m| m.mv))?,
+            ),
+        };
+        let mut rest = if evs.is_empty() {
+            self.compute_heuristically_ordered_moves(board)
+        } else {
+            evs
+        };
+        check_and_reposition_first(&mut rest, table_suggestion);
+        Ok(MovePicker::ordered(
+            pvs.into_iter().chain(rest).dedup().collect(),
+            self.move_lists,
+        ))
+    }
+}
 
-            (None, Some(evs)) => {
-                let mut mvs = evs.into_iter().map(|sm| sm.mv.clone()).collect_vec();
-                check_and_reposition_first(&mut mvs, table_suggestion);
-                mvs
-            }
+/// Expand compact moves created in the given position.
+fn expand_all<B: EvalChessBoard>(
+    board: &B,
+    moves: impl Iterator<Item = CompactMove>,
+) -> Result<Vec<Move>> {
+    moves.map(|m| m.expand(board)).collect()
+}
 
-            (Some(pvs_ref), Some(evs_ref)) => {
-                let pvs = pvs_ref.iter().map(|m| m.mv.clone()).collect_vec();
-                let mut evs = evs_ref.iter().map(|m| m.mv.clone()).collect_vec();
-                check_and_reposition_first(&mut evs, table_suggestion);
-                pvs.into_iter().chain(evs.into_iter()).dedup().collect()
-            }
-        }
+/// Expand a path of compact moves which is ordered deepest move first
+/// in the context of the position at the start of the path.
+fn expand_path<B: EvalChessBoard>(root: &mut B, path: &[CompactMove]) -> Result<Vec<Move>> {
+    let mut expanded = Vec::with_capacity(path.len());
+    let outcome = path.iter().rev().try_for_each(|mv| -> Result<()> {
+        let mv = mv.expand(root)?;
+        root.make_checked(mv.clone())?;
+        expanded.push(mv);
+        Ok(())
+    });
+    for _ in 0..expanded.len() {
+        root.unmake()?;
     }
+    expanded.reverse();
+    outcome.map(|_| expanded)
 }
 
 fn check_and_reposition_first(dest: &mut Vec<Move>, to_insert: Option<TableSuggestion>) {
@@ -1,24 +1,30 @@
+use crate::search::history::HistoryTable;
 use crate::{BitBoard, EvalChessBoard, Piece, Piece::*};
 use myopic_board::{Move, Move::*, Reflectable, Side, Square};
 
+/// Quiet moves have their history score divided by this
+/// before it is added to their positional score.
+const HISTORY_DIVISOR: i32 = 8;
+
 /// A function which approximately evaluates the quality
 /// of a move within the context of the given position.
 /// It can be used to decide the search order of legal
 /// moves for a position.
 pub trait MoveQualityEstimator<B: EvalChessBoard> {
     /// Assign a heuristic score to the given move in the
-    /// context of the given position. The score is agnostic
+    /// context of the given position and the history of
+    /// cutoffs in the search so far. The score is agnostic
     /// of the side to move, i.e. high magnitude positive
     /// score is always better and high magnitude negative
     /// score is always worse.
-    fn estimate(&self, board: &mut B, mv: &Move) -> i32;
+    fn estimate(&self, board: &mut B, mv: &Move, history: &HistoryTable) -> i32;
 }
 
 /// Simplest estimator which simply evaluates all moves
 /// as equal.
 pub struct ConstantEstimator;
 impl<B: EvalChessBoard> MoveQualityEstimator<B> for ConstantEstimator {
-    fn estimate(&self, _board: &mut B, _mv: &Move) -> i32 {
+    fn estimate(&self, _board: &mut B, _mv: &Move, _history: &HistoryTable) -> i32 {
         0
     }
 }
@@ -41,7 +47,8 @@ pub struct EstimatorImpl;
 //
 // The positional moves are those left over when other categories are computed
 // and their sub-ordering is according to the delta in position value according
-// to the tables.
+// to the tables combined with how often the move has caused a cutoff elsewhere
+// in the search.
 //
 // Special moves (castling, enpassant, promotions) don't really need sub-ordering
 //
@@ -50,17 +57,19 @@ pub struct EstimatorImpl;
 // is scored according to the delta between the piece values. For now ignore
 // potential pins.
 impl<B: EvalChessBoard> MoveQualityEstimator<B> for EstimatorImpl {
-    fn estimate(&self, board: &mut B, mv: &Move) -> i32 {
+    fn estimate(&self, board: &mut B, mv: &Move, history: &HistoryTable) -> i32 {
         match get_category(board, mv) {
             MoveCategory::GoodExchange(n) => 30_000 + n,
             MoveCategory::Special => 20_000,
-            MoveCategory::Positional(n) => 10_000 + n,
+            MoveCategory::Positional(n) => 10_000 + n + history.score(mv) / HISTORY_DIVISOR,
             MoveCategory::BadExchange(n) => n,
         }
     }
 }
 
-enum MoveCategory {
+/// The category a move is placed in for ordering, also used to decide
+/// how much the search depth of a late move can be reduced.
+pub(crate) enum MoveCategory {
     // Wraps the see exchange value, > 0
     GoodExchange(i32),
     Special,
@@ -70,7 +79,7 @@ enum MoveCategory {
     BadExchange(i32),
 }
 
-fn get_category<B: EvalChessBoard>(board: &mut B, mv: &Move) -> MoveCategory {
+pub(crate) fn get_category<B: EvalChessBoard>(board: &mut B, mv: &Move) -> MoveCategory {
     match mv {
         Enpassant { .. } | Castle { .. } | Promotion { .. } => MoveCategory::Special,
         &Standard {
@@ -144,3 +153,28 @@ fn parity(side: Side) -> i32 {
         Side::Black => -1,
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{EstimatorImpl, MoveQualityEstimator};
+    use crate::search::history::HistoryTable;
+    use crate::{ChessBoard, EvalBoard};
+    use anyhow::Result;
+
+    #[test]
+    fn history_raises_positional_estimate() -> Result<()> {
+        let mut board = EvalBoard::start();
+        let (mv, other) = (board.parse_uci("a2a3")?, board.parse_uci("h2h3")?);
+        let mut history = HistoryTable::default();
+        let before = EstimatorImpl.estimate(&mut board, &mv, &history);
+        history.update(&mv, &[other], 8);
+        let after = EstimatorImpl.estimate(&mut board, &mv, &history);
+        assert!(before < after, "{} {}", before, after);
+        // History never lifts a positional move out of its category
+        for _ in 0..1000 {
+            history.update(&mv, &[], 20);
+        }
+        assert!(EstimatorImpl.estimate(&mut board, &mv, &history) < 20_000);
+        Ok(())
+    }
+}
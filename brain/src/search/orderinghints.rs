@@ -1,26 +1,28 @@
 use crate::search::negascout::SearchResponse;
 use crate::EvalChessBoard;
 use itertools::Itertools;
-use myopic_board::{Move, MoveComputeType};
+use myopic_board::{CompactMove, Move, MoveComputeType};
 use std::cmp::Ordering;
 use std::collections::HashMap;
 
 const SHALLOW_EVAL_BRANCHING: usize = 5;
 
 /// Precomputed suggested moves to aid in move ordering
-/// for the search
+/// for the search, the moves are stored in compact form
+/// keyed by the sequence of moves from the root which
+/// leads to the position they were created in.
 pub struct OrderingHints<B: EvalChessBoard> {
     /// The root position from which all move sequences
     /// start from
     root: B,
     /// Principal variation moves which are the highest
     /// priority moves to try
-    pvs: HashMap<Vec<Move>, Vec<PVMove>>,
+    pvs: HashMap<Vec<CompactMove>, Vec<PVMove>>,
     /// Shallow evaluation collections must contain
     /// all the legal moves in the position and allow
     /// a more accurate complete ordering compared to
     /// the heuristic approach
-    evs: HashMap<Vec<Move>, Vec<SEMove>>,
+    evs: HashMap<Vec<CompactMove>, Vec<SEMove>>,
 }
 
 impl<B: EvalChessBoard> OrderingHints<B> {
@@ -36,11 +38,16 @@ impl<B: EvalChessBoard> OrderingHints<B> {
         self.populate_shallow_eval_impl(&mut self.root.clone(), depth, vec![])
     }
 
-    fn populate_shallow_eval_impl(&mut self, board: &mut B, depth: usize, precursors: Vec<Move>) {
+    fn populate_shallow_eval_impl(
+        &mut self,
+        board: &mut B,
+        depth: usize,
+        precursors: Vec<CompactMove>,
+    ) {
         let curr_level = OrderingHints::compute_shallow_eval(board);
         let next_paths = curr_level
             .iter()
-            .map(|m| m.mv.clone())
+            .map(|m| m.mv)
             .take(SHALLOW_EVAL_BRANCHING)
             .collect_vec();
 
@@ -49,8 +56,8 @@ impl<B: EvalChessBoard> OrderingHints<B> {
         if depth > 0 {
             for mv in next_paths {
                 let mut next_precursors = precursors.clone();
-                next_precursors.push(mv.clone());
-                board.make(mv).unwrap();
+                next_precursors.push(mv);
+                board.make(mv.expand(board).unwrap()).unwrap();
                 self.populate_shallow_eval_impl(board, depth - 1, next_precursors);
                 board.unmake().unwrap();
             }
@@ -63,31 +70,29 @@ impl<B: EvalChessBoard> OrderingHints<B> {
             root.make(mv).unwrap();
             let SearchResponse { eval, .. } = -super::negascout::search(root, 0).unwrap();
             let mv_made = root.unmake().unwrap();
-            dest.push(SEMove { mv: mv_made, eval });
+            dest.push(SEMove {
+                mv: mv_made.compact(),
+                eval,
+            });
         }
         dest.sort();
         dest.reverse();
         dest
     }
 
-    pub fn get_pvs(&self, mvs: &Vec<Move>) -> Option<&Vec<PVMove>> {
+    pub fn get_pvs(&self, mvs: &Vec<CompactMove>) -> Option<&Vec<PVMove>> {
         self.pvs.get(mvs)
     }
 
     pub fn add_pv(&mut self, depth: usize, pv: &Vec<Move>) {
-        for (i, mv) in pv.iter().enumerate() {
+        let pv = pv.iter().map(|m| m.compact()).collect_vec();
+        for (i, &mv) in pv.iter().enumerate() {
             let precursors = pv.iter().cloned().take(i).collect_vec();
-            self.add_pv_impl(
-                &precursors,
-                PVMove {
-                    mv: mv.clone(),
-                    depth,
-                },
-            );
+            self.add_pv_impl(&precursors, PVMove { mv, depth });
         }
     }
 
-    fn add_pv_impl(&mut self, mvs: &Vec<Move>, pv: PVMove) {
+    fn add_pv_impl(&mut self, mvs: &Vec<CompactMove>, pv: PVMove) {
         match self.pvs.get_mut(mvs) {
             None => {
                 self.pvs.insert(mvs.clone(), vec![pv]);
@@ -100,11 +105,11 @@ impl<B: EvalChessBoard> OrderingHints<B> {
         }
     }
 
-    pub fn get_evs(&self, mvs: &Vec<Move>) -> Option<&Vec<SEMove>> {
+    pub fn get_evs(&self, mvs: &Vec<CompactMove>) -> Option<&Vec<SEMove>> {
         self.evs.get(mvs)
     }
 
-    pub fn set_evs(&mut self, mvs: Vec<Move>, mut evs: Vec<SEMove>) {
+    pub fn set_evs(&mut self, mvs: Vec<CompactMove>, mut evs: Vec<SEMove>) {
         evs.sort();
         evs.reverse();
         self.evs.insert(mvs, evs);
@@ -114,7 +119,7 @@ impl<B: EvalChessBoard> OrderingHints<B> {
 // Shallow eval move
 #[derive(Clone, PartialEq, Eq)]
 pub struct SEMove {
-    pub mv: Move,
+    pub mv: CompactMove,
     pub eval: i32,
 }
 
@@ -133,7 +138,7 @@ impl Ord for SEMove {
 // Principal variation move
 #[derive(Clone, PartialEq, Eq)]
 pub struct PVMove {
-    pub mv: Move,
+    pub mv: CompactMove,
     pub depth: usize,
 }
 
@@ -0,0 +1,237 @@
+use crate::search::ordering::MoveCategory;
+use std::cmp;
+
+/// Parameters controlling null move pruning in the negascout search. In
+/// a non pv node we pass the turn to the opponent and search the result
+/// at a reduced depth, if our position is still good enough to cause a
+/// beta cutoff then we assume a real move would too and prune the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NullMovePruning {
+    /// Whether null moves are tried at all
+    pub enabled: bool,
+    /// The minimum remaining depth at which a null move will be tried
+    pub min_depth: usize,
+    /// The reduction applied to the null move search on top of the ply
+    /// taken by the null move itself
+    pub base_reduction: usize,
+    /// The reduction grows by one ply for every multiple of this
+    /// divisor in the remaining depth
+    pub depth_divisor: usize,
+    /// At or beyond this remaining depth a null move cutoff is only
+    /// trusted after being confirmed by a reduced depth search of the
+    /// node with null moves disabled, guarding against zugzwang
+    pub verification_depth: usize,
+}
+
+impl Default for NullMovePruning {
+    fn default() -> Self {
+        NullMovePruning {
+            enabled: true,
+            min_depth: 3,
+            base_reduction: 2,
+            depth_divisor: 4,
+            verification_depth: 6,
+        }
+    }
+}
+
+impl NullMovePruning {
+    /// Parameters which turn null move pruning off.
+    pub fn disabled() -> NullMovePruning {
+        NullMovePruning {
+            enabled: false,
+            ..NullMovePruning::default()
+        }
+    }
+
+    /// The number of plies the null move search is reduced by given the
+    /// depth remaining in the node it is made from.
+    pub fn reduction(&self, depth_remaining: usize) -> usize {
+        self.base_reduction + depth_remaining / self.depth_divisor.max(1)
+    }
+}
+
+/// The largest depth and move index the reduction table distinguishes,
+/// larger values share the reductions of this bound.
+const REDUCTION_TABLE_BOUND: usize = 64;
+
+/// Parameters controlling late move reductions in the negascout search.
+/// Moves ordered late in a node are unlikely to be best and so they are
+/// searched with a zero window at a depth reduced by an amount which
+/// grows with the remaining depth and the index of the move. If one of
+/// them fails high it is searched again at full depth.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LateMoveReductions {
+    /// Whether late moves are reduced at all
+    pub enabled: bool,
+    /// The number of moves searched at full depth in each node before
+    /// any reductions are applied
+    pub min_move_index: usize,
+    /// The minimum remaining depth at which moves are reduced
+    pub min_depth: usize,
+    /// The constant term of each entry in the reduction table
+    pub base: f64,
+    /// The product of the logarithms of the depth and move index is
+    /// divided by this to give the variable term of each table entry
+    pub divisor: f64,
+    /// A move is reduced one ply less for each multiple of this in its
+    /// history score, or one ply more for a negative score
+    pub history_divisor: i32,
+}
+
+impl Default for LateMoveReductions {
+    fn default() -> Self {
+        LateMoveReductions {
+            enabled: true,
+            min_move_index: 4,
+            min_depth: 3,
+            base: 0.75,
+            divisor: 2.25,
+            history_divisor: 8_192,
+        }
+    }
+}
+
+impl LateMoveReductions {
+    /// Parameters which turn late move reductions off.
+    pub fn disabled() -> LateMoveReductions {
+        LateMoveReductions {
+            enabled: false,
+            ..LateMoveReductions::default()
+        }
+    }
+
+    /// Precompute the reductions for every depth and move index.
+    pub fn table(&self) -> ReductionTable {
+        let bound = REDUCTION_TABLE_BOUND;
+        let mut reductions = vec![0; bound * bound];
+        for depth in 1..bound {
+            for index in 1..bound {
+                let log_product = (depth as f64).ln() * (index as f64).ln();
+                let reduction = self.base + log_product / self.divisor;
+                reductions[depth * bound + index] = reduction.max(0.0) as usize;
+            }
+        }
+        ReductionTable {
+            params: *self,
+            reductions,
+        }
+    }
+}
+
+/// Late move reductions precomputed from the parameters, the base entry
+/// is adjusted according to the category and history of each move.
+pub struct ReductionTable {
+    params: LateMoveReductions,
+    reductions: Vec<usize>,
+}
+
+impl ReductionTable {
+    /// Whether a move at the given index in a node with the given depth
+    /// remaining may be reduced, regardless of the move itself.
+    pub fn applies(&self, depth_remaining: usize, move_index: usize) -> bool {
+        self.params.enabled
+            && depth_remaining >= self.params.min_depth
+            && move_index >= self.params.min_move_index
+    }
+
+    /// The number of plies to reduce the search of a move by. Exchanges
+    /// which win material and special moves are never reduced while
+    /// moves which lose material are reduced further, moves in a node on
+    /// the principal variation are reduced less. The reduction always
+    /// leaves at least one ply to search.
+    pub(crate) fn reduction(
+        &self,
+        depth_remaining: usize,
+        move_index: usize,
+        category: &MoveCategory,
+        history: i32,
+        pv_node: bool,
+    ) -> usize {
+        if !self.applies(depth_remaining, move_index) {
+            return 0;
+        }
+        let bound = REDUCTION_TABLE_BOUND - 1;
+        let base = self.reductions
+            [depth_remaining.min(bound) * REDUCTION_TABLE_BOUND + move_index.min(bound)]
+            as i32;
+        let adjusted = match category {
+            MoveCategory::GoodExchange(_) | MoveCategory::Special => return 0,
+            MoveCategory::Positional(_) => base,
+            MoveCategory::BadExchange(_) => base + 1,
+        } - history / self.params.history_divisor.max(1)
+            - pv_node as i32;
+        let max_reduction = depth_remaining.saturating_sub(2);
+        cmp::min(cmp::max(adjusted, 0) as usize, max_reduction)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{LateMoveReductions, NullMovePruning};
+    use crate::search::ordering::MoveCategory;
+
+    #[test]
+    fn reduction_grows_with_depth() {
+        let params = NullMovePruning::default();
+        assert_eq!(2, params.reduction(3));
+        assert_eq!(3, params.reduction(4));
+        assert_eq!(3, params.reduction(7));
+        assert_eq!(4, params.reduction(8));
+    }
+
+    #[test]
+    fn zero_divisor_does_not_panic() {
+        let params = NullMovePruning {
+            depth_divisor: 0,
+            ..NullMovePruning::default()
+        };
+        assert_eq!(5, params.reduction(3));
+    }
+
+    #[test]
+    fn late_moves_reduced_more_at_greater_depth() {
+        let table = LateMoveReductions::default().table();
+        let positional = MoveCategory::Positional(0);
+        assert_eq!(0, table.reduction(8, 2, &positional, 0, false));
+        assert_eq!(1, table.reduction(3, 4, &positional, 0, false));
+        assert_eq!(3, table.reduction(8, 20, &positional, 0, false));
+        assert_eq!(4, table.reduction(8, 200, &positional, 0, false));
+        // Never reduce into the quiescent search
+        assert_eq!(1, table.reduction(3, 60, &positional, 0, false));
+    }
+
+    #[test]
+    fn reduction_depends_on_category_and_history() {
+        let table = LateMoveReductions::default().table();
+        let positional = MoveCategory::Positional(0);
+        let good = MoveCategory::GoodExchange(100);
+        let bad = MoveCategory::BadExchange(-200);
+        assert_eq!(0, table.reduction(8, 20, &good, 0, false));
+        assert_eq!(0, table.reduction(8, 20, &MoveCategory::Special, 0, false));
+        assert_eq!(4, table.reduction(8, 20, &bad, 0, false));
+        assert_eq!(1, table.reduction(8, 20, &positional, 16_384, false));
+        assert_eq!(5, table.reduction(8, 20, &positional, -16_384, false));
+        assert_eq!(2, table.reduction(8, 20, &positional, 0, true));
+    }
+
+    #[test]
+    fn shallow_min_depth_never_reduces_into_quiescent_search() {
+        let table = LateMoveReductions {
+            min_depth: 1,
+            ..LateMoveReductions::default()
+        }
+        .table();
+        let bad = MoveCategory::BadExchange(-200);
+        assert_eq!(0, table.reduction(1, 20, &bad, -16_384, false));
+        assert_eq!(0, table.reduction(2, 20, &bad, -16_384, false));
+        assert_eq!(1, table.reduction(3, 20, &bad, -16_384, false));
+    }
+
+    #[test]
+    fn disabled_reductions() {
+        let table = LateMoveReductions::disabled().table();
+        let positional = MoveCategory::Positional(0);
+        assert_eq!(0, table.reduction(8, 20, &positional, 0, false));
+    }
+}
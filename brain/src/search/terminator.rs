@@ -8,6 +8,13 @@ use std::time::Duration;
 /// which combines both checks.
 pub trait SearchTerminator {
     fn should_terminate(&self, ctx: &SearchContext) -> bool;
+
+    /// Called when the search of the root at the given depth fails low, that
+    /// is the eval is at most the given upper bound which is below the window
+    /// around the eval from the previous iteration. The best move found so far
+    /// may be losing so a terminator managing time could allow the search to
+    /// run for longer. By default this is ignored.
+    fn on_fail_low(&self, _depth: usize, _previous_eval: i32, _upper_bound: i32) {}
 }
 
 impl SearchTerminator for Duration {
@@ -1,11 +1,15 @@
-use crate::Move;
+use crate::CompactMove;
 use anyhow::{anyhow, Result};
 
 // Let's estimate 24 bytes per table entry
 const MAX_ENTRIES: usize = 30_000_000;
 
+/// Entries are stored alongside the full hash of the position they
+/// were created for so that positions which share an index can be
+/// distinguished. Moves are stored in their compact form and must be
+/// expanded in the position the entry matches.
 pub struct TranspositionTable {
-    inner: Vec<Option<TreeNode>>,
+    inner: Vec<Option<(u64, TreeNode)>>,
 }
 
 impl TranspositionTable {
@@ -23,12 +27,15 @@ impl TranspositionTable {
 
     pub fn get(&self, k: u64) -> Option<&TreeNode> {
         let index = self.index(k);
-        self.inner[index].as_ref().filter(|&m| m.matches(k))
+        match &self.inner[index] {
+            Some((hash, node)) if *hash == k => Some(node),
+            _ => None,
+        }
     }
 
     pub fn insert(&mut self, k: u64, v: TreeNode) {
         let index = self.index(k);
-        self.inner[index] = Some(v);
+        self.inner[index] = Some((k, v));
     }
 
     fn index(&self, k: u64) -> usize {
@@ -41,31 +48,20 @@ pub enum TreeNode {
     Pv {
         depth: u8,
         eval: i32,
-        optimal_path: Vec<Move>,
+        optimal_path: Vec<CompactMove>,
     },
     Cut {
         depth: u8,
         beta: i32,
-        cutoff_move: Move,
+        cutoff_move: CompactMove,
     },
     All {
         depth: u8,
         eval: i32,
-        best_move: Move,
+        best_move: CompactMove,
     },
 }
 impl TreeNode {
-    fn matches(&self, hash: u64) -> bool {
-        match self {
-            TreeNode::Cut { cutoff_move, .. } => cutoff_move.source() == hash,
-            TreeNode::All { best_move, .. } => best_move.source() == hash,
-            TreeNode::Pv { optimal_path, .. } => optimal_path
-                .first()
-                .map(|m| m.source() == hash)
-                .unwrap_or(false),
-        }
-    }
-
     pub fn depth(&self) -> usize {
         (match self {
             &TreeNode::Pv { depth, .. } => depth,
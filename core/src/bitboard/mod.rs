@@ -17,6 +17,7 @@ mod iterator;
 /// example if we know a piece to reside on a particular square we can
 /// use a bitboard to to capture the available moves for that piece.
 #[derive(Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BitBoard(pub u64);
 impl BitBoard {
     /// Check if this bitboard contains a particular square.
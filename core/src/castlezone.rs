@@ -10,6 +10,7 @@ use enumset::*;
 /// Represents one of the four different areas on a chessboard where
 /// the special castling move can take place (two for each side).
 #[derive(Debug, EnumSetType, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[rustfmt::skip]
 pub enum CastleZone { WK, WQ, BK, BQ }
 
@@ -0,0 +1,218 @@
+use anyhow::{anyhow, Result};
+
+use crate::bitboard::BitBoard;
+use crate::castlezone::CastleZone;
+use crate::pieces::Piece;
+use crate::reflectable::Reflectable;
+use crate::square::Square;
+use crate::Side;
+
+/// The squares the kings and rooks start on, which determine the squares
+/// involved in each castling move. In standard chess these are fixed but
+/// in Chess960 they depend on the randomised start position, although in
+/// both cases the king and rook finish on the same squares. The squares
+/// are given for white, black mirrors them on the eighth rank.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Castling {
+    chess960: bool,
+    king: Square,
+    kingside_rook: Square,
+    queenside_rook: Square,
+}
+
+impl Castling {
+    /// The castling squares of standard chess.
+    pub const STANDARD: Castling = Castling {
+        chess960: false,
+        king: Square::E1,
+        kingside_rook: Square::H1,
+        queenside_rook: Square::A1,
+    };
+
+    /// Create the castling squares for a Chess960 game from the squares
+    /// on the first rank the white king and rooks start on. The king must
+    /// lie strictly between the two rooks.
+    pub fn chess960(
+        king: Square,
+        kingside_rook: Square,
+        queenside_rook: Square,
+    ) -> Result<Castling> {
+        let first_rank = BitBoard::RANKS[0];
+        if !(first_rank.contains(king)
+            && first_rank.contains(kingside_rook)
+            && first_rank.contains(queenside_rook)
+            && kingside_rook < king
+            && king < queenside_rook)
+        {
+            Err(anyhow!(
+                "Invalid castling squares: king {} rooks {} {}",
+                king,
+                kingside_rook,
+                queenside_rook
+            ))
+        } else {
+            Ok(Castling {
+                chess960: true,
+                king,
+                kingside_rook,
+                queenside_rook,
+            })
+        }
+    }
+
+    /// Whether these squares belong to a Chess960 game, in which case
+    /// castling moves use the king takes rook uci notation.
+    pub fn is_chess960(self) -> bool {
+        self.chess960
+    }
+
+    /// Returns a triple containing the king which moves in the given zone
+    /// along with its required start square followed by the square it
+    /// will finish on, which may be the same.
+    pub fn king_data(self, zone: CastleZone) -> (Piece, Square, Square) {
+        let dest = if is_kingside(zone) {
+            Square::G1
+        } else {
+            Square::C1
+        };
+        let side = zone.side();
+        (
+            Piece::king(side),
+            on_back_rank(self.king, side),
+            on_back_rank(dest, side),
+        )
+    }
+
+    /// Returns a triple containing the rook which moves in the given zone
+    /// along with its required start square followed by the square it
+    /// will finish on, which may be the same.
+    pub fn rook_data(self, zone: CastleZone) -> (Piece, Square, Square) {
+        let (src, dest) = if is_kingside(zone) {
+            (self.kingside_rook, Square::F1)
+        } else {
+            (self.queenside_rook, Square::D1)
+        };
+        let side = zone.side();
+        (
+            Piece::rook(side),
+            on_back_rank(src, side),
+            on_back_rank(dest, side),
+        )
+    }
+
+    /// Returns the set of exactly two squares which denote the required
+    /// locations of the king and rook for the given zone.
+    pub fn source_squares(self, zone: CastleZone) -> BitBoard {
+        self.king_data(zone).1 | self.rook_data(zone).1
+    }
+
+    /// Returns the squares which are required to be free of any pieces
+    /// other than the castling king and rook for the castle to be legal.
+    pub fn unoccupied_requirement(self, zone: CastleZone) -> BitBoard {
+        let (_, king_src, king_dest) = self.king_data(zone);
+        let (_, rook_src, rook_dest) = self.rook_data(zone);
+        (span(king_src, king_dest) | span(rook_src, rook_dest)) - king_src - rook_src
+    }
+
+    /// Returns the squares which are required to be free of enemy control
+    /// for the castle in the given zone to be legal.
+    pub fn uncontrolled_requirement(self, zone: CastleZone) -> BitBoard {
+        let (_, king_src, king_dest) = self.king_data(zone);
+        span(king_src, king_dest)
+    }
+}
+
+impl Default for Castling {
+    fn default() -> Self {
+        Castling::STANDARD
+    }
+}
+
+/// The castling squares are mirrored for the two sides so they are
+/// unchanged by reflection.
+impl Reflectable for Castling {
+    fn reflect(&self) -> Self {
+        *self
+    }
+}
+
+fn is_kingside(zone: CastleZone) -> bool {
+    zone == CastleZone::WK || zone == CastleZone::BK
+}
+
+fn on_back_rank(square: Square, side: Side) -> Square {
+    match side {
+        Side::White => square,
+        Side::Black => square.reflect(),
+    }
+}
+
+/// The squares on the line between the two given squares inclusive.
+fn span(a: Square, b: Square) -> BitBoard {
+    if a == b {
+        a.lift()
+    } else {
+        BitBoard::cord(a, b)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn standard_matches_castle_zones() {
+        for zone in CastleZone::iter() {
+            let castling = Castling::STANDARD;
+            assert_eq!(zone.king_data(), castling.king_data(zone));
+            assert_eq!(zone.rook_data(), castling.rook_data(zone));
+            assert_eq!(zone.source_squares(), castling.source_squares(zone));
+            assert_eq!(
+                zone.unoccupied_requirement(),
+                castling.unoccupied_requirement(zone)
+            );
+            assert_eq!(
+                zone.uncontrolled_requirement(),
+                castling.uncontrolled_requirement(zone)
+            );
+        }
+    }
+
+    #[test]
+    fn chess960_squares() {
+        let castling = Castling::chess960(Square::F1, Square::G1, Square::B1).unwrap();
+        assert!(castling.is_chess960());
+        assert_eq!(
+            (Piece::BK, Square::F8, Square::G8),
+            castling.king_data(CastleZone::BK)
+        );
+        assert_eq!(
+            (Piece::BR, Square::G8, Square::F8),
+            castling.rook_data(CastleZone::BK)
+        );
+        assert_eq!(
+            BitBoard::EMPTY,
+            castling.unoccupied_requirement(CastleZone::WK)
+        );
+        assert_eq!(
+            Square::F1 | Square::G1,
+            castling.uncontrolled_requirement(CastleZone::WK)
+        );
+        assert_eq!(
+            Square::C1 | Square::D1 | Square::E1,
+            castling.unoccupied_requirement(CastleZone::WQ)
+        );
+        assert_eq!(
+            Square::C1 | Square::D1 | Square::E1 | Square::F1,
+            castling.uncontrolled_requirement(CastleZone::WQ)
+        );
+    }
+
+    #[test]
+    fn invalid_chess960_squares() {
+        assert!(Castling::chess960(Square::F1, Square::B1, Square::G1).is_err());
+        assert!(Castling::chess960(Square::F2, Square::G2, Square::B2).is_err());
+        assert!(Castling::chess960(Square::A1, Square::B1, Square::C1).is_err());
+    }
+}
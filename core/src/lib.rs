@@ -6,24 +6,29 @@ extern crate lazy_static;
 pub extern crate enumset;
 
 mod bitboard;
+mod castling;
 mod castlezone;
 pub mod hash;
 mod pieces;
 mod reflectable;
+pub mod render;
 mod square;
 
 use anyhow::anyhow;
 pub use bitboard::constants;
 pub use bitboard::BitBoard;
+pub use castling::Castling;
 pub use castlezone::CastleZone;
 pub use pieces::Piece;
 pub use reflectable::Reflectable;
+pub use render::RenderOptions;
 pub use square::Square;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
 /// Represents the two different teams in a game of chess.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Side {
     White,
     Black,
@@ -12,6 +12,7 @@ mod sliding;
 /// Value type wrapping a single integer representing one of the 12
 /// different pieces in a game of chess.
 #[derive(Debug, EnumSetType, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[rustfmt::skip]
 pub enum Piece {
     WP, WN, WB, WR, WQ, WK,
@@ -0,0 +1,150 @@
+use crate::{BitBoard, Piece, Side, Square};
+
+#[rustfmt::skip]
+const ASCII_PIECES: [char; 12] = [
+    'P', 'N', 'B', 'R', 'Q', 'K',
+    'p', 'n', 'b', 'r', 'q', 'k',
+];
+
+#[rustfmt::skip]
+const UNICODE_PIECES: [char; 12] = [
+    '♙', '♘', '♗', '♖', '♕', '♔',
+    '♟', '♞', '♝', '♜', '♛', '♚',
+];
+
+const FILES: [char; 8] = ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'];
+
+/// Options controlling how a diagram of the board is drawn. The output
+/// contains no control characters so it can be written directly to a
+/// log or a terminal.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// Draw with unicode chess symbols rather than ascii letters
+    pub unicode: bool,
+    /// Label the ranks on the left and the files along the bottom
+    pub labels: bool,
+    /// Mark the back rank of the side to move where there is one
+    pub active_marker: bool,
+    /// Squares to draw in brackets, for example those of the last move
+    pub highlights: BitBoard,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            unicode: false,
+            labels: true,
+            active_marker: true,
+            highlights: BitBoard::EMPTY,
+        }
+    }
+}
+
+impl Piece {
+    /// The symbol used to draw this piece in a diagram, ascii symbols
+    /// are the letters used in FEN.
+    pub fn symbol(self, unicode: bool) -> char {
+        if unicode {
+            UNICODE_PIECES[self as usize]
+        } else {
+            ASCII_PIECES[self as usize]
+        }
+    }
+}
+
+impl BitBoard {
+    /// Draw this set of squares as a grid with the white side at the
+    /// bottom.
+    pub fn render(self, options: &RenderOptions) -> String {
+        let occupied = if options.unicode { '■' } else { 'x' };
+        render_grid(options, None, |sq| {
+            Some(occupied).filter(|_| self.contains(sq))
+        })
+    }
+}
+
+/// Draw a grid of squares with rank 8 at the top and the a file on the
+/// left, each square is drawn with the given symbol or a dot if there
+/// is none. The side to move is marked next to its back rank if given.
+pub fn render_grid<F>(options: &RenderOptions, active: Option<Side>, symbol: F) -> String
+where
+    F: Fn(Square) -> Option<char>,
+{
+    let empty = if options.unicode { '·' } else { '.' };
+    let marker = if options.unicode { '◀' } else { '<' };
+    let active_rank = active
+        .filter(|_| options.active_marker)
+        .map(|side| match side {
+            Side::White => 0,
+            Side::Black => 7,
+        });
+    let mut lines = Vec::with_capacity(9);
+    for rank in (0..8).rev() {
+        let mut line = String::new();
+        if options.labels {
+            line.push_str(&format!("{} ", rank + 1));
+        }
+        for file in 0..8 {
+            let square = Square::from_index(8 * rank + 7 - file);
+            let c = symbol(square).unwrap_or(empty);
+            if options.highlights.contains(square) {
+                line.push_str(&format!("[{}]", c));
+            } else {
+                line.push_str(&format!(" {} ", c));
+            }
+        }
+        if active_rank == Some(rank) {
+            line.push_str(&format!(" {}", marker));
+        }
+        lines.push(line.trim_end().to_owned());
+    }
+    if options.labels {
+        let files = FILES.iter().map(|f| format!(" {} ", f)).collect::<String>();
+        lines.push(format!("  {}", files).trim_end().to_owned());
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ascii_bitboard() {
+        let options = RenderOptions {
+            highlights: Square::E4.lift(),
+            ..RenderOptions::default()
+        };
+        let expected = vec![
+            "8  .  .  .  .  .  .  .  x",
+            "7  .  .  .  .  .  .  .  .",
+            "6  .  .  .  .  .  .  .  .",
+            "5  .  .  .  .  .  .  .  .",
+            "4  .  .  .  . [x] .  .  .",
+            "3  .  .  .  .  .  .  .  .",
+            "2  .  .  .  .  .  .  .  .",
+            "1  x  .  .  .  .  .  .  .",
+            "   a  b  c  d  e  f  g  h",
+        ];
+        let board = Square::A1 | Square::H8 | Square::E4;
+        assert_eq!(expected.join("\n"), board.render(&options));
+    }
+
+    #[test]
+    fn unicode_without_labels() {
+        let options = RenderOptions {
+            unicode: true,
+            labels: false,
+            ..RenderOptions::default()
+        };
+        let grid = render_grid(&options, Some(Side::Black), |sq| match sq {
+            Square::E8 => Some(Piece::BK.symbol(true)),
+            Square::E1 => Some(Piece::WK.symbol(true)),
+            _ => None,
+        });
+        let lines = grid.lines().collect::<Vec<_>>();
+        assert_eq!(8, lines.len());
+        assert_eq!(" ·  ·  ·  ·  ♚  ·  ·  ·  ◀", lines[0]);
+        assert_eq!(" ·  ·  ·  ·  ♔  ·  ·  ·", lines[7]);
+    }
+}
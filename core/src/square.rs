@@ -4,6 +4,7 @@ use std::str::FromStr;
 
 /// Type representing a square on a chessboard.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[rustfmt::skip]
 pub enum Square {
     H1, G1, F1, E1, D1, C1, B1, A1,
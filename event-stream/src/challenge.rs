@@ -7,6 +7,7 @@ use crate::validity::TimeValidity;
 
 const STANDARD_VARIANT_KEY: &'static str = "standard";
 const FEN_VARIANT_KEY: &'static str = "fromPosition";
+const CHESS960_VARIANT_KEY: &'static str = "chess960";
 
 pub struct ChallengeService {
     client: LichessClient,
@@ -57,7 +58,8 @@ impl ChallengeService {
     }
 
     fn is_legal_challenge(&self, time_control: &ClockTimeControl, variant: &Variant) -> bool {
-        self.time_validity.is_valid(time_control) && variant.key.as_str() == STANDARD_VARIANT_KEY
-            || variant.key.as_str() == FEN_VARIANT_KEY
+        self.time_validity.is_valid(time_control)
+            && [STANDARD_VARIANT_KEY, FEN_VARIANT_KEY, CHESS960_VARIANT_KEY]
+                .contains(&variant.key.as_str())
     }
 }
@@ -36,7 +36,7 @@ impl RequestPayload {
         RequestPayload {
             start_fen: match initial_position {
                 InitalPosition::Start => None,
-                InitalPosition::CustomFen(fen) => Some(fen.clone()),
+                InitalPosition::CustomFen(fen) | InitalPosition::Chess960(fen) => Some(fen.clone()),
             },
             payload_type: format!("uciSequence"),
             sequence: sequence.to_string(),
@@ -38,6 +38,12 @@ pub struct GameFull {
     pub state: GameState,
     #[serde(rename = "initialFen")]
     pub initial_fen: String,
+    pub variant: Variant,
+}
+
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct Variant {
+    pub key: String,
 }
 
 #[derive(Deserialize, Debug, Clone, Eq, PartialEq)]
@@ -161,7 +167,12 @@ mod test {
                 "bdraw": false,
                 "status": "started"
             },
-            "initialFen": "startpos"
+            "initialFen": "startpos",
+            "variant": {
+                "key": "standard",
+                "name": "Standard",
+                "short": "Std"
+            }
         }"#;
 
         match serde_json::from_str::<GameEvent>(json) {
@@ -198,6 +209,12 @@ mod test {
                         },
                         content.state
                     );
+                    assert_eq!(
+                        Variant {
+                            key: format!("standard")
+                        },
+                        content.variant
+                    );
                 }
                 _ => panic!("Wrong type {:?}", event),
             },
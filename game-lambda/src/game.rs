@@ -12,6 +12,7 @@ use crate::TimeConstraints;
 use crate::timing::Timing;
 
 const STARTED_STATUS: &'static str = "started";
+const CHESS960_VARIANT_KEY: &'static str = "chess960";
 const CREATED_STATUS: &'static str = "created";
 const MOVE_LATENCY_MS: u64 = 200;
 const MIN_COMPUTE_TIME_MS: u64 = 200;
@@ -37,6 +38,22 @@ pub trait ComputeService {
 pub enum InitalPosition {
     Start,
     CustomFen(String),
+    /// The start position of a Chess960 game with the castling rights
+    /// given in Shredder-FEN so the castling rooks are unambiguous
+    Chess960(String),
+}
+
+/// Chess960 start positions are normalised to Shredder-FEN as lichess
+/// provides them in X-FEN which cannot always identify the castling rooks.
+fn initial_position(game_full: &GameFull) -> Result<InitalPosition> {
+    Ok(if game_full.variant.key.as_str() == CHESS960_VARIANT_KEY {
+        let start = Board::from_fen_chess960(game_full.initial_fen.as_str())?;
+        InitalPosition::Chess960(start.to_fen())
+    } else if game_full.initial_fen.as_str() == "startpos" {
+        InitalPosition::Start
+    } else {
+        InitalPosition::CustomFen(game_full.initial_fen.clone())
+    })
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -167,11 +184,7 @@ where
             } else {
                 return Err(anyhow!("Unrecognized names"));
             },
-            initial_position: if game_full.initial_fen.as_str() == "startpos" {
-                InitalPosition::Start
-            } else {
-                InitalPosition::CustomFen(game_full.initial_fen)
-            },
+            initial_position: initial_position(&game_full)?,
         });
         self.process_game_state(game_full.state)
     }
@@ -300,3 +313,73 @@ where
             .ok_or(anyhow!("Metadata not initialized"))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use myopic_brain::ChessBoard;
+
+    use crate::events::GameEvent;
+
+    use super::InitalPosition;
+
+    #[test]
+    fn chess960_initial_position() {
+        let json = r#"{
+            "type": "gameFull",
+            "id": "123",
+            "white": {
+                "id": "myopic-bot",
+                "name": "myopic-bot",
+                "title": "BOT",
+                "rating": 1500,
+                "provisional": true
+            },
+            "black": {
+                "id": "th0masb",
+                "name": "th0masb",
+                "title": null,
+                "rating": 1500,
+                "provisional": true
+            },
+            "clock": {
+                "initial": 1200000,
+                "increment": 10000
+            },
+            "state": {
+                "moves": "e2e4 e7e5 g1f3 g8f6 f1c4 f8c5",
+                "wtime": 1000,
+                "btime": 1000,
+                "winc": 0,
+                "binc": 0,
+                "wdraw": false,
+                "bdraw": false,
+                "status": "started"
+            },
+            "initialFen": "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "variant": {
+                "key": "chess960",
+                "name": "Chess960",
+                "short": "960"
+            }
+        }"#;
+
+        let game_full = match serde_json::from_str::<GameEvent>(json) {
+            Ok(GameEvent::GameFull { content }) => content,
+            event => panic!("Unexpected event {:?}", event),
+        };
+        let initial_position = super::initial_position(&game_full).unwrap();
+        assert_eq!(
+            InitalPosition::Chess960(
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1".to_owned()
+            ),
+            initial_position
+        );
+        // Castling is encoded as the king capturing its rook
+        let moves = format!("{} e1h1", game_full.state.moves);
+        let board = crate::position::get(&initial_position, moves.as_str()).unwrap();
+        assert_eq!(
+            "rnbqk2r/pppp1ppp/5n2/2b1p3/2B1P3/5N2/PPPP1PPP/RNBQ1RK1 b ha - 5 4",
+            board.to_fen()
+        );
+    }
+}
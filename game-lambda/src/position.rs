@@ -1,5 +1,5 @@
 use anyhow::Result;
-use myopic_brain::{Board, ChessBoard, EvalBoard};
+use myopic_brain::{Board, EvalBoard, GameRecord, STARTPOS_FEN};
 
 use crate::game::InitalPosition;
 
@@ -7,10 +7,15 @@ pub fn get(
     initial: &InitalPosition,
     uci_sequence: &str,
 ) -> Result<EvalBoard<Board>> {
-    let mut position = match initial {
-        InitalPosition::Start => myopic_brain::start(),
-        InitalPosition::CustomFen(fen) => fen.parse()?,
+    let record = GameRecord {
+        start_fen: match initial {
+            InitalPosition::Start => STARTPOS_FEN.to_owned(),
+            InitalPosition::CustomFen(fen) | InitalPosition::Chess960(fen) => fen.clone(),
+        },
+        moves: uci_sequence
+            .split_whitespace()
+            .map(|m| m.to_owned())
+            .collect(),
     };
-    position.play_uci(uci_sequence)?;
-    Ok(EvalBoard::builder(position).build())
+    Ok(EvalBoard::builder(record.restore()?).build())
 }
@@ -82,6 +82,8 @@ struct ComputeMoveOutput {
     #[serde(rename = "searchDurationMillis")]
     search_duration_millis: u64,
     eval: i32,
+    #[serde(rename = "mateIn")]
+    mate_in: Option<i32>,
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
@@ -105,6 +107,7 @@ fn move_compute_handler(
             best_move: outcome.best_move.uci_format(),
             depth_searched: outcome.depth,
             eval: outcome.eval,
+            mate_in: outcome.mate_in(),
             search_duration_millis: outcome.time.as_millis() as u64,
         })
         .map_err(|err| HandlerError::from(err.to_string().as_str()))?;
@@ -148,18 +151,12 @@ fn extract_params(e: &ComputeMoveEvent) -> SearchParameters<SearchTerminator> {
             terminator,
             table_size,
             ..
-        } => SearchParameters {
-            terminator,
-            table_size,
-        },
+        } => SearchParameters::new(terminator, table_size),
         &ComputeMoveEvent::UciSequence {
             terminator,
             table_size,
             ..
-        } => SearchParameters {
-            terminator,
-            table_size,
-        },
+        } => SearchParameters::new(terminator, table_size),
     }
 }
 